@@ -5,11 +5,35 @@ pub use winit::{
 
 use std::time::{Duration, Instant};
 
+/// springs are integrated in steps of this size (in seconds) to stay stable on slow frames
+const SPRING_STEP: f32 = 1.0 / 240.0;
+/// a spring is considered at rest once both its displacement and velocity are below this
+const SPRING_REST_THRESHOLD: f32 = 0.01;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PlaybackMode {
+    /// plays the animation a single time and then stays at the end values
+    Once,
+    /// restarts the animation from the beginning every time it finishes
+    Loop,
+    /// plays the animation forwards and then backwards, forever
+    PingPong,
+}
+
 #[derive(Debug, Clone)]
 pub struct Animation {
     start_time: Instant,
     duration: Duration,
     transitions: Vec<Transition>,
+    /// the current value of every spring transition
+    positions: Vec<f32>,
+    /// the current velocity of every spring transition
+    velocities: Vec<f32>,
+    last_update: Instant,
+    /// when the animation got paused, `None` while it is running
+    paused_at: Option<Instant>,
+    mode: PlaybackMode,
+    /// only ever set in `PlaybackMode::Once`
     pub done: bool,
 }
 
@@ -18,36 +42,148 @@ impl Animation {
         Animation {
             start_time: Instant::now(),
             duration,
+            positions: transitions.iter().map(|t| t.calculate(0.0)).collect(),
+            velocities: vec![0.0; transitions.len()],
+            last_update: Instant::now(),
             transitions,
+            paused_at: None,
+            mode: PlaybackMode::Once,
             done: false,
         }
     }
 
     pub fn reset(&mut self) {
         self.done = false;
-        self.start_time = Instant::now()
+        self.start_time = Instant::now();
+        self.last_update = Instant::now();
+        self.positions = self.transitions.iter().map(|t| t.calculate(0.0)).collect();
+        self.velocities = vec![0.0; self.transitions.len()];
+        if self.paused_at.is_some() {
+            self.paused_at = Some(Instant::now());
+        }
+    }
+
+    pub fn mode(&self) -> PlaybackMode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: PlaybackMode) {
+        self.mode = mode;
+        if mode != PlaybackMode::Once {
+            self.done = false;
+        }
+    }
+
+    pub fn pause(&mut self) {
+        if self.paused_at.is_none() {
+            self.paused_at = Some(Instant::now());
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if let Some(paused_at) = self.paused_at.take() {
+            self.start_time += paused_at.elapsed();
+            self.last_update = Instant::now();
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    /// false once the animation is done or while it is paused, looping animations never finish
+    pub fn is_running(&self) -> bool {
+        !self.done && !self.is_paused()
+    }
+
+    /// the time the animation has been running for, excluding the time it was paused
+    fn elapsed(&self) -> Duration {
+        match self.paused_at {
+            Some(paused_at) => paused_at - self.start_time,
+            None => self.start_time.elapsed(),
+        }
     }
 
     pub fn animate(&mut self) -> Vec<f32> {
         if self.done {
             return self.transitions.iter().map(|t| t.get_done()).collect();
         }
-        let progress = (self.start_time.elapsed().as_millis() as f32
-            / self.duration.as_millis() as f32)
-            .min(1.0);
-        if progress == 1.0 {
+        let progress = self.progress();
+        let dt = if self.is_paused() {
+            0.0
+        } else {
+            self.last_update.elapsed().as_secs_f32()
+        };
+        self.last_update = Instant::now();
+        self.step(progress, dt)
+    }
+
+    /// moves the springs `dt` seconds forward and calculates every value at `progress`
+    fn step(&mut self, progress: f32, dt: f32) -> Vec<f32> {
+        let mut settled = true;
+        let mut values = Vec::with_capacity(self.transitions.len());
+        for (i, transition) in self.transitions.iter().enumerate() {
+            match transition {
+                Transition::Spring { to, stiffness, damping, .. } => {
+                    let mut remaining = dt;
+                    while remaining > 0.0 {
+                        let step = remaining.min(SPRING_STEP);
+                        let acceleration = -stiffness * (self.positions[i] - to)
+                            - damping * self.velocities[i];
+                        self.velocities[i] += acceleration * step;
+                        self.positions[i] += self.velocities[i] * step;
+                        remaining -= step;
+                    }
+                    if (self.positions[i] - to).abs() > SPRING_REST_THRESHOLD
+                        || self.velocities[i].abs() > SPRING_REST_THRESHOLD
+                    {
+                        settled = false;
+                    }
+                    values.push(self.positions[i]);
+                }
+                t => values.push(t.calculate(progress)),
+            }
+        }
+
+        if self.mode == PlaybackMode::Once && progress == 1.0 && settled {
             self.done = true;
         }
-        self.transitions
-            .iter()
-            .map(|t| t.calculate(progress))
-            .collect()
+        values
+    }
+
+    /// how far along the current cycle is, depending on the playback mode
+    fn progress(&self) -> f32 {
+        if self.duration.as_nanos() == 0 {
+            return 1.0;
+        }
+        let cycles = self.elapsed().as_secs_f32() / self.duration.as_secs_f32();
+        match self.mode {
+            PlaybackMode::Once => cycles.min(1.0),
+            PlaybackMode::Loop => cycles.fract(),
+            PlaybackMode::PingPong if (cycles as u64) % 2 == 0 => cycles.fract(),
+            PlaybackMode::PingPong => 1.0 - cycles.fract(),
+        }
     }
 }
 
 #[derive(Clone, Debug)]
 pub enum Transition {
     Linear(f32, f32),
+    /// starts slow and speeds up (`p^3`)
+    EaseIn(f32, f32),
+    /// starts fast and slows down (`1 - (1 - p)^3`)
+    EaseOut(f32, f32),
+    /// slow at both ends (`3p^2 - 2p^3`)
+    EaseInOut(f32, f32),
+    /// a damped harmonic oscillator pulling the value towards `to`.
+    ///
+    /// Springs ignore the duration of the animation and only finish once they come to rest.
+    Spring {
+        from: f32,
+        to: f32,
+        stiffness: f32,
+        damping: f32,
+    },
 }
 
 impl Transition {
@@ -57,11 +193,85 @@ impl Transition {
                 let d = to - from;
                 from + d * progress
             }
+            Self::EaseIn(from, to) => {
+                let d = to - from;
+                from + d * progress.powi(3)
+            }
+            Self::EaseOut(from, to) => {
+                let d = to - from;
+                from + d * (1.0 - (1.0 - progress).powi(3))
+            }
+            Self::EaseInOut(from, to) => {
+                let d = to - from;
+                from + d * (3.0 * progress.powi(2) - 2.0 * progress.powi(3))
+            }
+            // springs are integrated by `Animation`, this is only used for the start and end values
+            Self::Spring { from, to, .. } => {
+                let d = to - from;
+                from + d * progress
+            }
         }
     }
     pub fn get_done(&self) -> f32 {
         match self {
-            Self::Linear(_, end) => *end,
+            Self::Linear(_, end)
+            | Self::EaseIn(_, end)
+            | Self::EaseOut(_, end)
+            | Self::EaseInOut(_, end)
+            | Self::Spring { to: end, .. } => *end,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Animation, Transition};
+    use std::time::Duration;
+
+    const TWEENS: [fn(f32, f32) -> Transition; 4] = [
+        Transition::Linear,
+        Transition::EaseIn,
+        Transition::EaseOut,
+        Transition::EaseInOut,
+    ];
+
+    #[test]
+    fn tweens_start_at_from_and_end_at_to() {
+        for tween in TWEENS.iter() {
+            let transition = tween(10.0, -5.0);
+            assert_eq!(transition.calculate(0.0), 10.0, "{:?}", transition);
+            assert_eq!(transition.calculate(1.0), -5.0, "{:?}", transition);
+        }
+    }
+
+    #[test]
+    fn tweens_are_monotonic() {
+        for tween in TWEENS.iter() {
+            for (from, to) in [(0.0, 100.0), (100.0, 0.0)].iter() {
+                let transition = tween(*from, *to);
+                let values: Vec<f32> = (0..=100).map(|i| transition.calculate(i as f32 / 100.0)).collect();
+                for pair in values.windows(2) {
+                    let step = pair[1] - pair[0];
+                    assert!(step * (to - from) >= 0.0, "{:?} moves backwards: {:?}", transition, pair);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn springs_come_to_rest_at_their_target() {
+        let spring = Transition::Spring { from: 0.0, to: 100.0, stiffness: 170.0, damping: 26.0 };
+        // without a duration the animation is only waiting for the spring
+        let mut animation = Animation::new(Duration::from_secs(0), vec![spring]);
+        let mut value = 0.0;
+        for _ in 0..600 {
+            if animation.done {
+                break;
+            }
+            value = animation.step(1.0, 1.0 / 60.0)[0];
         }
+        assert!(animation.done, "the spring is still moving after 10 seconds");
+        assert!((value - 100.0).abs() < 0.1, "the spring stopped at {}", value);
+        assert_eq!(animation.animate(), vec![100.0]);
     }
 }
@@ -12,6 +12,8 @@ use crate::{
 
 use chrono::prelude::*;
 
+use std::time::Duration;
+
 pub struct Appbar {
     active_ws: usize
 }
@@ -37,6 +39,11 @@ impl Application for Appbar {
             Key::One => self.active_ws = 1,
             Key::Two => self.active_ws = 2,
             Key::Three => self.active_ws = 3,
+            Key::F11 => {
+                let fullscreen = !r.is_fullscreen();
+                r.set_fullscreen(fullscreen);
+                r.hide_cursor(fullscreen);
+            },
             _ => {}
         }
         None
@@ -65,6 +72,8 @@ impl Appbar {
         r.move_cursor(0.0, -2.0, |r| {
             r.text(&Local::now().format(fmt).to_string(), Color::BLACK);
         });
+        // the clock changes on the next second
+        r.schedule_repaint_in(Duration::from_millis(1000 - Local::now().timestamp_subsec_millis().min(999) as u64));
     }
 
     fn render_ws(&self, r: &mut Renderer, id: usize) {
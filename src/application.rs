@@ -1,30 +1,88 @@
 use winit::{
-    event::{ElementState, Event, VirtualKeyCode, WindowEvent, MouseButton},
+    event::{ElementState, Event, VirtualKeyCode, WindowEvent, MouseButton, MouseScrollDelta},
     event_loop::ControlFlow,
-    event_loop::EventLoop,
+    event_loop::{EventLoop, EventLoopProxy},
+    platform::run_return::EventLoopExtRunReturn,
     window::WindowBuilder,
 };
 
 use glium::{glutin::ContextBuilder, Display, Program};
 
-use std::hash::Hash;
+use std::{collections::HashMap, hash::Hash, path::PathBuf, time::Instant};
 
+use crate::color::Color;
+use crate::error::{AppError, RendererError};
 use crate::renderer::Renderer;
-use crate::key::Key;
+use crate::key::{Key, KeyEvent};
 use crate::shaders::{FRAGMENT_SHADER, VERTEX_SHADER};
+use crate::window::WindowConfig;
 
-/// Used for debugging
-const RENDER_ONCE: bool = false;
+/// how many pixels one line of a mouse wheel scrolls
+const SCROLL_LINE_HEIGHT: f32 = 40.0;
 
-pub trait Application {
+/// how many frames an application renders, see `Application::render_mode`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RenderMode {
+    /// renders a frame whenever something changed
+    Continuous,
+    /// renders a single frame and afterwards only handles events, useful for debugging the rendering
+    Once,
+    /// renders the given number of frames and afterwards only handles events
+    NFrames(u32),
+}
+
+impl RenderMode {
+    /// whether another frame may be rendered after `frame_count` frames
+    fn allows_frame(&self, frame_count: u64) -> bool {
+        match self {
+            Self::Continuous => true,
+            Self::Once => frame_count < 1,
+            Self::NFrames(n) => frame_count < *n as u64,
+        }
+    }
+}
+
+impl Default for RenderMode {
+    fn default() -> Self {
+        Self::Continuous
+    }
+}
+
+/// whether the window gets closed, see `Application::on_close_requested`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CloseAction {
+    Allow,
+    /// keeps the window open, e.g. to ask for confirmation first
+    Prevent,
+}
+
+/// `U` is the type of the user events that can be sent to the event loop, see `set_event_proxy`
+pub trait Application<U: 'static = ()> {
+    /// called once before `init` with a proxy that can send user events to the event loop from other threads
+    fn set_event_proxy(&mut self, _proxy: EventLoopProxy<U>) {}
     fn init(&mut self, _renderer: &mut Renderer) {}
     fn render(&mut self, renderer: &mut Renderer);
+    fn render_mode(&self) -> RenderMode {
+        RenderMode::Continuous
+    }
+    /// the settings of the window, applied before `window`
+    fn window_config(&self) -> WindowConfig {
+        WindowConfig::new()
+    }
     fn window(&mut self, w: WindowBuilder) -> WindowBuilder {
         w
     }
     fn on_event(
         &mut self,
-        _event: Event<()>,
+        _event: Event<U>,
+        _r: &mut Renderer,
+    ) -> Option<ControlFlow> {
+        None
+    }
+    /// called for every event sent through the proxy passed to `set_event_proxy`
+    fn on_user_event(
+        &mut self,
+        _event: U,
         _r: &mut Renderer,
     ) -> Option<ControlFlow> {
         None
@@ -43,6 +101,14 @@ pub trait Application {
     ) -> Option<ControlFlow> {
         None
     }
+    /// called instead of `on_key_down` for the presses the os generates while a key is held
+    fn on_key_repeat(
+        &mut self,
+        _key: Key,
+        _r: &mut Renderer,
+    ) -> Option<ControlFlow> {
+        None
+    }
     fn on_key_up(
         &mut self,
         _key: Key,
@@ -52,112 +118,336 @@ pub trait Application {
     }
     fn on_mouse_down(
         &mut self,
-        _left: bool,
+        _button: MouseButton,
         _x: f32,
         _y: f32,
         _r: &mut Renderer,
     ) -> Option<ControlFlow> {
         None
     }
+    /// called when the mouse moves onto the hitbox `id`
+    fn on_hover_enter(&mut self, _id: u32, _r: &mut Renderer) {}
+    /// called when the mouse leaves the hitbox `id`, before `on_hover_enter` of the next hitbox
+    fn on_hover_exit(&mut self, _id: u32, _r: &mut Renderer) {}
+    /// called when the user tries to close the window
+    fn on_close_requested(&mut self, _r: &mut Renderer) -> CloseAction {
+        CloseAction::Allow
+    }
+    /// called for every file that gets dropped onto the window
+    fn on_file_drop(
+        &mut self,
+        _path: PathBuf,
+        _r: &mut Renderer,
+    ) -> Option<ControlFlow> {
+        None
+    }
+    /// `width` and `height` are the new size of the window in logical pixels
+    fn on_resize(
+        &mut self,
+        _width: f32,
+        _height: f32,
+        _r: &mut Renderer,
+    ) -> Option<ControlFlow> {
+        None
+    }
 }
 
-pub trait ApplicationWrapper<T: Application> {
+pub trait ApplicationWrapper<T: Application<U>, U: 'static = ()> {
+    /// runs the application, errors get printed to stderr
     fn run(self);
+    /// runs the application until the window gets closed and returns why it stopped if it failed
+    fn run_with_result(self) -> Result<(), AppError>;
     fn call_render(&mut self, renderer: &mut Renderer);
 }
 
-impl<T: 'static> ApplicationWrapper<T> for T
+/// an application with multiple windows, each one rendered by its own `Application`
+pub trait MultiWindowApplication {
+    fn create_windows(&mut self, event_loop: &EventLoop<()>) -> Vec<(WindowBuilder, Box<dyn Application>)>;
+}
+
+pub trait MultiWindowApplicationWrapper<T: MultiWindowApplication> {
+    fn run_multi(self);
+}
+
+fn create_renderer<U: 'static>(wb: WindowBuilder, transparent: bool, ev: &EventLoop<U>) -> Result<Renderer, RendererError> {
+    let mut cb = ContextBuilder::new();
+    if transparent {
+        // the framebuffer needs an alpha channel for the window to be see-through
+        cb = cb.with_pixel_format(24, 8);
+    }
+    let display = Display::new(wb, cb, ev)?;
+    let program = Program::from_source(&display, VERTEX_SHADER, FRAGMENT_SHADER, None)?;
+
+    Renderer::new(display, program)
+}
+
+/// renders a single frame of `app`, e.g. to drive a headless renderer in tests
+pub fn render_frame<U: 'static, A: Application<U> + ?Sized>(app: &mut A, renderer: &mut Renderer) {
+    renderer.next_frame();
+    let prev_hot_id = renderer.hot_id;
+    renderer.hot_id = renderer.get_hit(renderer.mouse.x as f32, renderer.mouse.y as f32);
+    if renderer.hot_id != prev_hot_id {
+        if let Some(id) = prev_hot_id {
+            app.on_hover_exit(id, renderer);
+        }
+        if let Some(id) = renderer.hot_id {
+            app.on_hover_enter(id, renderer);
+        }
+    }
+    renderer.update_hover_start();
+    renderer.handle_focus_navigation();
+    renderer.clear_hitboxes();
+    app.render(renderer);
+    renderer.draw_toasts();
+    renderer.draw_tooltips();
+    renderer.dispatch_events();
+    if renderer.debug_mode {
+        renderer.draw_debug_hitboxes(Color::new(255, 0, 0));
+    }
+    renderer.done();
+}
+
+/// a frame only gets rendered if something changed or a scheduled repaint is due, as long as the render mode allows it
+fn should_render(mode: RenderMode, renderer: &Renderer) -> bool {
+    mode.allows_frame(renderer.frame_count())
+        && (renderer.needs_redraw() || renderer.scheduled_repaint().map_or(false, |at| at <= Instant::now()))
+}
+
+/// keeps rendering while a redraw is requested, otherwise waits for the next event or the earliest scheduled repaint
+fn next_control_flow<'a>(windows: impl Iterator<Item = (RenderMode, &'a Renderer)>) -> ControlFlow {
+    let mut scheduled_repaint: Option<Instant> = None;
+    for (mode, renderer) in windows {
+        if !mode.allows_frame(renderer.frame_count()) {
+            continue;
+        }
+        if renderer.needs_redraw() {
+            return ControlFlow::Poll;
+        }
+        if let Some(at) = renderer.scheduled_repaint() {
+            scheduled_repaint = Some(scheduled_repaint.map_or(at, |earliest| earliest.min(at)));
+        }
+    }
+    scheduled_repaint.map(ControlFlow::WaitUntil).unwrap_or(ControlFlow::Wait)
+}
+
+fn handle_window_event<U: 'static, A: Application<U> + ?Sized>(app: &mut A, event: &WindowEvent, renderer: &mut Renderer) -> ControlFlow {
+    let control_flow = match event {
+        WindowEvent::CloseRequested => match app.on_close_requested(renderer) {
+            CloseAction::Allow => ControlFlow::Exit,
+            CloseAction::Prevent => ControlFlow::Poll,
+        },
+        WindowEvent::Resized(size) => {
+            let size = size.to_logical::<f32>(renderer.scale_factor());
+            app.on_resize(size.width, size.height, renderer).unwrap_or(ControlFlow::Poll)
+        },
+        WindowEvent::CursorMoved { position, .. } => {
+            // the ui works in logical pixels
+            let position = position.to_logical::<f64>(renderer.scale_factor());
+            renderer.mouse.x = position.x;
+            renderer.mouse.y = position.y;
+            ControlFlow::Poll
+        },
+        WindowEvent::MouseWheel { delta, .. } => {
+            renderer.mouse.scroll_delta -= match delta {
+                MouseScrollDelta::LineDelta(_, y) => y * SCROLL_LINE_HEIGHT,
+                MouseScrollDelta::PixelDelta(position) => position.to_logical::<f32>(renderer.scale_factor()).y,
+            };
+            ControlFlow::Poll
+        },
+        WindowEvent::MouseInput { state, button, .. } => {
+            match (state, button) {
+                (ElementState::Released, MouseButton::Left) => {
+                    renderer.mouse.lmouseclick = true;
+                    renderer.mouse.lmousedown = false;
+                    ControlFlow::Poll
+                },
+                (ElementState::Released, MouseButton::Right) => {
+                    renderer.mouse.rmouseclick = true;
+                    ControlFlow::Poll
+                },
+                (ElementState::Released, MouseButton::Middle) => {
+                    renderer.mouse.mmouseclick = true;
+                    ControlFlow::Poll
+                },
+                (ElementState::Pressed, mb) => {
+                    if *mb == MouseButton::Left {
+                        renderer.mouse.lmousedown = true;
+                    }
+                    app.on_mouse_down(
+                        *mb,
+                        renderer.mouse.x as f32, 
+                        renderer.mouse.y as f32, 
+                        renderer
+                    ).unwrap_or(ControlFlow::Poll)
+                },
+                _ => ControlFlow::Poll
+            }
+        },
+        WindowEvent::DroppedFile(path) => app.on_file_drop(path.clone(), renderer).unwrap_or(ControlFlow::Poll),
+        WindowEvent::ModifiersChanged(state) => {
+            renderer.modifiers = *state;
+            ControlFlow::Poll
+        },
+        WindowEvent::Focused(false) => {
+            // keys released while another window has the focus never send a release event
+            renderer.held_keys.clear();
+            ControlFlow::Poll
+        },
+        WindowEvent::ReceivedCharacter(c) => {
+            renderer.input.push(*c);
+            app.on_text_input(*c, renderer).unwrap_or(ControlFlow::Poll)
+        },
+        WindowEvent::KeyboardInput { input, .. } => {
+            input.virtual_keycode.map(Key::from).and_then(|key| {
+                match input.state {
+                    ElementState::Pressed => {
+                        let is_repeat = !renderer.held_keys.insert(key);
+                        renderer.keys.push(KeyEvent { key, is_repeat });
+                        if is_repeat {
+                            return app.on_key_repeat(key, renderer);
+                        }
+                        if key == Key::F12 {
+                            renderer.debug_mode = !renderer.debug_mode;
+                        }
+                        app.on_key_down(key, renderer)
+                    },
+                    ElementState::Released => {
+                        renderer.held_keys.remove(&key);
+                        app.on_key_up(key, renderer)
+                    },
+                }
+            })
+            .unwrap_or(ControlFlow::Poll)
+        }
+        _ => return ControlFlow::Poll,
+    };
+    // every handled event can change what gets rendered
+    renderer.request_redraw();
+    control_flow
+}
+
+impl<T: 'static, U: 'static> ApplicationWrapper<T, U> for T
 where
-    T: Application,
+    T: Application<U>,
 {
     fn call_render(&mut self, renderer: &mut Renderer) {
-        renderer.clear();
-        renderer.next_frame();
-        renderer.hot_id = renderer.get_hit(renderer.mouse.x as f32, renderer.mouse.y as f32);
-        renderer.mouse.lmouseclick = false;
-        renderer.mouse.rmouseclick = false;
-        renderer.clear_hitboxes();
-        self.render(renderer);
-        renderer.done();
+        render_frame(self, renderer);
     }
 
-    fn run(mut self) {
-        let ev = EventLoop::new();
-        let wb = self.window(WindowBuilder::new());
-        let cb = ContextBuilder::new();
-        let display = Display::new(wb, cb, &ev).unwrap();
-        let program = Program::from_source(&display, VERTEX_SHADER, FRAGMENT_SHADER, None).unwrap();
+    fn run(self) {
+        if let Err(e) = self.run_with_result() {
+            eprintln!("{}", e);
+        }
+    }
 
-        let mut renderer = Renderer::new(display, program);
+    fn run_with_result(mut self) -> Result<(), AppError> {
+        let mut ev = EventLoop::with_user_event();
+        let config = self.window_config();
+        let transparent = config.transparent;
+        let wb = self.window(config.apply(WindowBuilder::new()));
+        let mut renderer = create_renderer(wb, transparent, &ev)?;
 
+        self.set_event_proxy(ev.create_proxy());
         self.init(&mut renderer);
-        if RENDER_ONCE {
-            self.call_render(&mut renderer);
-        }
 
-        ev.run(move |event, _, control_flow| {
+        // unlike `run`, `run_return` gives control back once the loop exits
+        ev.run_return(|event, _, control_flow| {
+            if let Event::UserEvent(user_event) = event {
+                // the event probably changed the state of the application
+                renderer.request_redraw();
+                *control_flow = self.on_user_event(user_event, &mut renderer).unwrap_or(ControlFlow::Poll);
+                return;
+            }
+
             *control_flow = match &event {
-                Event::WindowEvent { event, .. } => match event {
-                    WindowEvent::CloseRequested => ControlFlow::Exit,
-                    WindowEvent::Resized(..) => ControlFlow::Poll,
-                    WindowEvent::CursorMoved { position, .. } => {
-                        renderer.mouse.x = position.x;
-                        renderer.mouse.y = position.y;
-                        ControlFlow::Poll
-                    },
-                    WindowEvent::MouseInput { state, button, .. } => {
-                        match (state, button) {
-                            (ElementState::Released, MouseButton::Left) => {
-                                renderer.mouse.lmouseclick = true;
-                                ControlFlow::Poll
-                            },
-                            (ElementState::Released, MouseButton::Right) => {
-                                renderer.mouse.lmouseclick = true;
-                                ControlFlow::Poll
-                            },
-                            (ElementState::Pressed, mb) => self.on_mouse_down(
-                                *mb == MouseButton::Left, 
-                                renderer.mouse.x as f32, 
-                                renderer.mouse.y as f32, 
-                                &mut renderer
-                            ).unwrap_or(ControlFlow::Poll),
-                            _ => ControlFlow::Poll
-                        }
-                    },
-                    WindowEvent::ModifiersChanged(state) => {
-                        renderer.modifiers = *state;
-                        ControlFlow::Poll
-                    },
-                    WindowEvent::ReceivedCharacter(c) => {
-                        renderer.input.push(*c);
-                        self.on_text_input(*c, &mut renderer).unwrap_or(ControlFlow::Poll)
-                    },
-                    WindowEvent::KeyboardInput { input, .. } => {
-                        input.virtual_keycode.map(Key::from).and_then(|key| {
-                            match input.state {
-                                ElementState::Pressed => {
-                                    renderer.keys.push(key);
-                                    self.on_key_down(key, &mut renderer)
-                                },
-                                ElementState::Released => self.on_key_up(key, &mut renderer),
-                            }
-                        })
-                        .unwrap_or(ControlFlow::Poll)
-                    }
-                    _ => ControlFlow::Poll,
-                },
+                Event::WindowEvent { event, .. } => handle_window_event(&mut self, event, &mut renderer),
                 Event::MainEventsCleared => {
-                    if !RENDER_ONCE {
+                    let mode = self.render_mode();
+                    if should_render(mode, &renderer) {
                         self.call_render(&mut renderer);
                     }
+                    next_control_flow(std::iter::once((mode, &renderer)))
+                }
+                Event::RedrawRequested(_) => {
+                    renderer.request_redraw();
                     ControlFlow::Poll
                 }
-                _ => ControlFlow::Poll,
+                _ => *control_flow,
             };
 
             if let Some(cf) = self.on_event(event, &mut renderer) {
                 *control_flow = cf;
             }
+            if renderer.exit_code().is_some() {
+                *control_flow = ControlFlow::Exit;
+            }
+        });
+
+        match renderer.exit_code() {
+            Some(code) if code != 0 => Err(AppError::Exit(code)),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<T: 'static> MultiWindowApplicationWrapper<T> for T
+where
+    T: MultiWindowApplication,
+{
+    fn run_multi(mut self) {
+        let ev = EventLoop::new();
+        let mut windows = HashMap::new();
+        for (wb, mut app) in self.create_windows(&ev) {
+            let config = app.window_config();
+            let transparent = config.transparent;
+            let wb = app.window(config.apply(wb));
+            let mut renderer = match create_renderer(wb, transparent, &ev) {
+                Ok(renderer) => renderer,
+                Err(e) => {
+                    // the other windows can still be shown
+                    eprintln!("failed to create the renderer: {}", e);
+                    continue;
+                }
+            };
+            app.init(&mut renderer);
+            let window_id = renderer.window_id().expect("The renderer has no window");
+            windows.insert(window_id, (app, renderer));
+        }
+
+        ev.run(move |event, _, control_flow| {
+            match &event {
+                Event::WindowEvent { window_id, event: window_event } => {
+                    let window_id = *window_id;
+                    if let Some((app, renderer)) = windows.get_mut(&window_id) {
+                        let mut cf = handle_window_event(app.as_mut(), window_event, renderer);
+                        if let Some(app_cf) = app.on_event(event, renderer) {
+                            cf = app_cf;
+                        }
+                        // exiting only closes the window the event belongs to
+                        if cf == ControlFlow::Exit {
+                            windows.remove(&window_id);
+                        }
+                    }
+                    if windows.is_empty() {
+                        *control_flow = ControlFlow::Exit;
+                    }
+                },
+                Event::MainEventsCleared => {
+                    for (app, renderer) in windows.values_mut() {
+                        if should_render(app.render_mode(), renderer) {
+                            render_frame(app.as_mut(), renderer);
+                        }
+                    }
+                    *control_flow = next_control_flow(windows.values().map(|(app, renderer)| (app.render_mode(), renderer)));
+                }
+                Event::RedrawRequested(window_id) => {
+                    if let Some((_, renderer)) = windows.get_mut(window_id) {
+                        renderer.request_redraw();
+                        *control_flow = ControlFlow::Poll;
+                    }
+                }
+                _ => {}
+            }
         });
     }
 }
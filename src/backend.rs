@@ -0,0 +1,52 @@
+use glium::{
+    backend::{Context, Facade},
+    Display, Frame, HeadlessRenderer,
+};
+use winit::{dpi::PhysicalSize, window::Window};
+
+use std::rc::Rc;
+
+/// what the renderer draws to
+pub enum Backend {
+    Window(Display),
+    /// an off-screen context without a window, e.g. for tests. `size` is in physical pixels
+    Headless { renderer: HeadlessRenderer, size: (u32, u32) },
+}
+
+impl Backend {
+    pub fn draw(&self) -> Frame {
+        match self {
+            Self::Window(display) => display.draw(),
+            Self::Headless { renderer, .. } => renderer.draw(),
+        }
+    }
+
+    /// calls `f` with the window, returns `None` for headless backends
+    pub fn with_window<T>(&self, f: impl FnOnce(&Window) -> T) -> Option<T> {
+        match self {
+            Self::Window(display) => Some(f(display.gl_window().window())),
+            Self::Headless { .. } => None,
+        }
+    }
+
+    pub fn scale_factor(&self) -> f64 {
+        self.with_window(|window| window.scale_factor()).unwrap_or(1.0)
+    }
+
+    /// the size of the window in physical pixels
+    pub fn inner_size(&self) -> PhysicalSize<u32> {
+        match self {
+            Self::Window(display) => display.gl_window().window().inner_size(),
+            Self::Headless { size, .. } => PhysicalSize::new(size.0, size.1),
+        }
+    }
+}
+
+impl Facade for Backend {
+    fn get_context(&self) -> &Rc<Context> {
+        match self {
+            Self::Window(display) => display.get_context(),
+            Self::Headless { renderer, .. } => renderer.get_context(),
+        }
+    }
+}
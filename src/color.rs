@@ -3,6 +3,7 @@ pub struct Color {
     r: u16,
     g: u16,
     b: u16,
+    a: u16,
 }
 
 impl Color {
@@ -10,7 +11,38 @@ impl Color {
     pub const BLACK: Color = Color::new(0, 0, 0);
 
     pub const fn new(r: u16, g: u16, b: u16) -> Self {
-        Self { r, g, b }
+        Self { r, g, b, a: 255 }
+    }
+
+    pub const fn rgba(r: u16, g: u16, b: u16, a: u16) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub fn r(&self) -> u16 {
+        self.r
+    }
+
+    pub fn g(&self) -> u16 {
+        self.g
+    }
+
+    pub fn b(&self) -> u16 {
+        self.b
+    }
+
+    pub fn a(&self) -> u16 {
+        self.a
+    }
+
+    /// blends this color with `other`, where `t = 0` is this color and `t = 1` is `other`
+    pub fn lerp(self, other: Color, t: f32) -> Self {
+        let mix = |from: u16, to: u16| (from as f32 + (to as f32 - from as f32) * t).round() as u16;
+        Self {
+            r: mix(self.r, other.r),
+            g: mix(self.g, other.g),
+            b: mix(self.b, other.b),
+            a: mix(self.a, other.a),
+        }
     }
 }
 
@@ -23,3 +55,14 @@ impl Into<[f32; 3]> for Color {
         ]
     }
 }
+
+impl Into<[f32; 4]> for Color {
+    fn into(self) -> [f32; 4] {
+        [
+            self.r as f32 / 255.0,
+            self.g as f32 / 255.0,
+            self.b as f32 / 255.0,
+            self.a as f32 / 255.0,
+        ]
+    }
+}
@@ -0,0 +1,210 @@
+use glium::backend::glutin::DisplayCreationError;
+
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ImageLoadError {
+    /// the file could not be read
+    Io(std::io::Error),
+    /// the file is not an image or uses an unsupported format
+    Decode(image::ImageError),
+}
+
+impl fmt::Display for ImageLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to read image: {}", e),
+            Self::Decode(e) => write!(f, "failed to decode image: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ImageLoadError {}
+
+impl From<std::io::Error> for ImageLoadError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<image::ImageError> for ImageLoadError {
+    fn from(e: image::ImageError) -> Self {
+        Self::Decode(e)
+    }
+}
+
+#[derive(Debug)]
+pub enum IconLoadError {
+    /// the data is not an image or uses an unsupported format
+    Decode(image::ImageError),
+    /// the decoded image can't be used as an icon
+    Invalid(winit::window::BadIcon),
+}
+
+impl fmt::Display for IconLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode(e) => write!(f, "failed to decode icon: {}", e),
+            Self::Invalid(e) => write!(f, "invalid icon: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for IconLoadError {}
+
+impl From<image::ImageError> for IconLoadError {
+    fn from(e: image::ImageError) -> Self {
+        Self::Decode(e)
+    }
+}
+
+impl From<winit::window::BadIcon> for IconLoadError {
+    fn from(e: winit::window::BadIcon) -> Self {
+        Self::Invalid(e)
+    }
+}
+
+#[derive(Debug)]
+pub enum ScreenshotError {
+    /// there is no frame, screenshots can only be taken while rendering
+    NoFrame,
+    /// the pixels could not be read back from the gpu
+    Read(glium::buffer::ReadError),
+    /// the image could not be encoded or written
+    Save(image::ImageError),
+}
+
+impl fmt::Display for ScreenshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoFrame => write!(f, "there is no frame to take a screenshot of"),
+            Self::Read(e) => write!(f, "failed to read the frame: {}", e),
+            Self::Save(e) => write!(f, "failed to save screenshot: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ScreenshotError {}
+
+impl From<glium::buffer::ReadError> for ScreenshotError {
+    fn from(e: glium::buffer::ReadError) -> Self {
+        Self::Read(e)
+    }
+}
+
+impl From<image::ImageError> for ScreenshotError {
+    fn from(e: image::ImageError) -> Self {
+        Self::Save(e)
+    }
+}
+
+#[derive(Debug)]
+pub enum HeadlessError {
+    /// the off-screen context could not be created
+    Creation(glium::glutin::CreationError),
+    /// the context doesn't support the required opengl version
+    Incompatible(glium::IncompatibleOpenGl),
+    /// the renderer could not be created on top of the context
+    Renderer(RendererError),
+}
+
+impl fmt::Display for HeadlessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Creation(e) => write!(f, "failed to create headless context: {}", e),
+            Self::Incompatible(e) => write!(f, "incompatible opengl context: {}", e),
+            Self::Renderer(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for HeadlessError {}
+
+impl From<glium::glutin::CreationError> for HeadlessError {
+    fn from(e: glium::glutin::CreationError) -> Self {
+        Self::Creation(e)
+    }
+}
+
+impl From<glium::IncompatibleOpenGl> for HeadlessError {
+    fn from(e: glium::IncompatibleOpenGl) -> Self {
+        Self::Incompatible(e)
+    }
+}
+
+impl From<RendererError> for HeadlessError {
+    fn from(e: RendererError) -> Self {
+        Self::Renderer(e)
+    }
+}
+
+#[derive(Debug)]
+pub enum RendererError {
+    /// one of the built-in shaders failed to compile
+    ShaderCompilation(glium::ProgramCreationError),
+    /// the default font could not be loaded
+    FontLoad(String),
+    /// the context doesn't support the required opengl version
+    ContextCreation(glium::IncompatibleOpenGl),
+    /// the window or its opengl context could not be created
+    WindowCreation(glium::glutin::CreationError),
+}
+
+impl fmt::Display for RendererError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ShaderCompilation(e) => write!(f, "failed to compile shader: {}", e),
+            Self::FontLoad(e) => write!(f, "failed to load font: {}", e),
+            Self::ContextCreation(e) => write!(f, "incompatible opengl context: {}", e),
+            Self::WindowCreation(e) => write!(f, "failed to create the window: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RendererError {}
+
+impl From<glium::ProgramCreationError> for RendererError {
+    fn from(e: glium::ProgramCreationError) -> Self {
+        Self::ShaderCompilation(e)
+    }
+}
+
+impl From<glium::IncompatibleOpenGl> for RendererError {
+    fn from(e: glium::IncompatibleOpenGl) -> Self {
+        Self::ContextCreation(e)
+    }
+}
+
+impl From<DisplayCreationError> for RendererError {
+    fn from(e: DisplayCreationError) -> Self {
+        match e {
+            DisplayCreationError::GlutinCreationError(e) => Self::WindowCreation(e),
+            DisplayCreationError::IncompatibleOpenGl(e) => Self::ContextCreation(e),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AppError {
+    /// the window or the renderer could not be created
+    Renderer(RendererError),
+    /// the application exited through `Renderer::exit_with_code` with a non-zero code
+    Exit(i32),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Renderer(e) => write!(f, "failed to create the renderer: {}", e),
+            Self::Exit(code) => write!(f, "the application exited with code {}", code),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<RendererError> for AppError {
+    fn from(e: RendererError) -> Self {
+        Self::Renderer(e)
+    }
+}
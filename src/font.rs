@@ -3,65 +3,329 @@
 //! https://learnopengl.com/In-Practice/Text-Rendering
 
 use freetype as ft;
-use std::{collections::HashMap, rc::Rc};
+use std::{borrow::Cow, collections::HashMap, rc::Rc};
 
 use glium::{
     texture::{ClientFormat, CompressedSrgbTexture2d, RawImage2d, Texture2d, Texture2dArray},
-    Display,
+    backend::Facade,
+    Rect,
 };
 
+/// the width of the glyph atlas in pixels, the height depends on the font size
+const ATLAS_WIDTH: usize = 512;
+/// the space between glyphs in the atlas, so sampling doesn't bleed into the neighbouring glyphs
+const ATLAS_PADDING: usize = 1;
+
 pub struct CharacterInfo {
     pub bearing: (i32, i32),
     pub size: (i32, i32),
     pub advance: i32,
-    pub texture: Texture2d,
+    /// the top left corner of the glyph in the atlas of the font
+    pub uv_min: (f32, f32),
+    /// the bottom right corner of the glyph in the atlas of the font
+    pub uv_max: (f32, f32),
+}
+
+/// the size of a piece of text rendered with a `Font`
+pub struct TextMetrics {
+    pub width: f32,
+    pub height: f32,
+    /// how far each character moves the cursor to the right
+    pub advance_widths: Vec<f32>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FontWeight {
+    Normal,
+    Bold,
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FontStyle {
+    Normal,
+    Italic,
+}
+
+impl Default for FontStyle {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+/// the data of the other faces of a font, they get packed into the same atlas as the regular face
+#[derive(Debug, Copy, Clone, Default)]
+pub struct FontVariants<'a> {
+    pub bold: Option<&'a [u8]>,
+    pub italic: Option<&'a [u8]>,
+    pub bold_italic: Option<&'a [u8]>,
 }
 
 pub struct Font {
     character_info: HashMap<char, CharacterInfo>,
+    pub bold: Option<HashMap<char, CharacterInfo>>,
+    pub italic: Option<HashMap<char, CharacterInfo>>,
+    pub bold_italic: Option<HashMap<char, CharacterInfo>>,
     pub size: u32,
+    /// a single texture containing every glyph, so text can be drawn with a single draw call.
+    ///
+    /// It is shared with the queued draw calls of the renderer.
+    pub atlas: Rc<Texture2d>,
+    /// where the next glyph added through `add_glyph` goes
+    atlas_cursor: AtlasCursor,
+    /// a copy of the atlas, so it can be uploaded again once it has to grow
+    atlas_pixels: Vec<u8>,
 }
 
-impl Font {
-    pub fn from_memory(display: &Display, buffer: &[u8], font_size: u32) -> Self {
-        let lib = ft::Library::init().expect("Failed to initialize FreeType library");
-        let face = lib
-            .new_memory_face(Rc::new(buffer.to_vec()), 0)
-            .expect("Font not found");
-
-        face.set_pixel_sizes(0, font_size).unwrap();
-
-        let mut character_info = HashMap::new();
-
-        for c in 0..127u8 {
-            face.load_char(c as usize, ft::face::LoadFlag::RENDER)
-                .unwrap();
-            let glyph = face.glyph();
-            let bitmap = glyph.bitmap();
-            let mut image = RawImage2d::from_raw_rgb(
-                bitmap.buffer().to_vec(),
-                (bitmap.width() as u32, bitmap.rows() as u32),
-            );
-            image.format = ClientFormat::U8;
-            let texture = Texture2d::new(display, image).unwrap();
-            character_info.insert(
-                c as char,
-                CharacterInfo {
-                    size: (bitmap.width(), bitmap.rows()),
-                    bearing: (glyph.bitmap_left(), glyph.bitmap_top()),
-                    advance: glyph.advance().x,
-                    texture,
-                },
-            );
+/// a glyph that was rendered by freetype, but not copied into the atlas yet
+struct RenderedGlyph {
+    c: char,
+    /// the top left corner in the atlas
+    position: (usize, usize),
+    pixels: Vec<u8>,
+    bearing: (i32, i32),
+    advance: i32,
+    size: (usize, usize),
+}
+
+impl RenderedGlyph {
+    fn copy_into(&self, atlas: &mut [u8]) {
+        let (x, y) = self.position;
+        let (width, rows) = self.size;
+        for row in 0..rows {
+            let start = (y + row) * ATLAS_WIDTH + x;
+            atlas[start..start + width].copy_from_slice(&self.pixels[row * width..(row + 1) * width]);
+        }
+    }
+
+    /// where the glyph ended up in an atlas of the given height
+    fn info(&self, atlas_height: usize) -> CharacterInfo {
+        let (x, y) = self.position;
+        let (width, rows) = self.size;
+        CharacterInfo {
+            size: (width as i32, rows as i32),
+            bearing: self.bearing,
+            advance: self.advance,
+            uv_min: (x as f32 / ATLAS_WIDTH as f32, y as f32 / atlas_height as f32),
+            uv_max: ((x + width) as f32 / ATLAS_WIDTH as f32, (y + rows) as f32 / atlas_height as f32),
         }
+    }
+}
 
-        Font {
+/// uploads the pixels of the atlas, which is `ATLAS_WIDTH` pixels wide
+fn create_atlas(display: &impl Facade, pixels: &[u8]) -> Result<Texture2d, String> {
+    let image = RawImage2d {
+        data: Cow::Borrowed(pixels),
+        width: ATLAS_WIDTH as u32,
+        height: (pixels.len() / ATLAS_WIDTH) as u32,
+        format: ClientFormat::U8,
+    };
+    Texture2d::new(display, image).map_err(|e| format!("failed to create the glyph atlas: {:?}", e))
+}
+
+/// where the next glyph gets placed in the atlas
+#[derive(Default, Clone, Copy)]
+struct AtlasCursor {
+    x: usize,
+    y: usize,
+    row_height: usize,
+}
+
+impl AtlasCursor {
+    /// returns the top left corner for a glyph of the given size, starting a new row if it doesn't fit
+    fn place(&mut self, width: usize, rows: usize) -> (usize, usize) {
+        if self.x + width > ATLAS_WIDTH {
+            self.x = 0;
+            self.y += self.row_height + ATLAS_PADDING;
+            self.row_height = 0;
+        }
+        let position = (self.x, self.y);
+        self.x += width + ATLAS_PADDING;
+        self.row_height = self.row_height.max(rows);
+        position
+    }
+}
+
+impl Font {
+    /// fails with a description of the problem if freetype can't load the font
+    pub fn from_memory(display: &impl Facade, buffer: &[u8], font_size: u32) -> Result<Self, String> {
+        Self::from_memory_with_variants(display, buffer, FontVariants::default(), font_size)
+    }
+
+    /// loads the regular face from `buffer` and the bold and italic faces from `variants`
+    pub fn from_memory_with_variants(display: &impl Facade, buffer: &[u8], variants: FontVariants, font_size: u32) -> Result<Self, String> {
+        // ascii plus the bullet used to mask passwords and the minus sign
+        let characters: Vec<char> = (0..127u8).map(|c| c as char).chain(['\u{2022}', '\u{2212}'].iter().copied()).collect();
+        Self::load(display, buffer, variants, &characters, font_size)
+    }
+
+    /// loads only the given characters of the font, e.g. the icons of an icon font
+    pub fn from_memory_with_chars(display: &impl Facade, buffer: &[u8], characters: &[char], font_size: u32) -> Result<Self, String> {
+        Self::load(display, buffer, FontVariants::default(), characters, font_size)
+    }
+
+    fn load(display: &impl Facade, buffer: &[u8], variants: FontVariants, characters: &[char], font_size: u32) -> Result<Self, String> {
+        let lib = ft::Library::init().map_err(|e| format!("failed to initialize freetype: {}", e))?;
+
+        // the glyphs of every face get rendered first and are then packed into rows of the atlas
+        let mut cursor = AtlasCursor::default();
+        let faces = [Some(buffer), variants.bold, variants.italic, variants.bold_italic];
+        let rendered: Vec<Option<Vec<RenderedGlyph>>> = faces
+            .iter()
+            .map(|data| data.map(|data| render_glyphs(&lib, data, characters, font_size, &mut cursor)).transpose())
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("failed to render the glyphs: {}", e))?;
+        let atlas_height = (cursor.y + cursor.row_height).max(1);
+
+        let mut atlas = vec![0u8; ATLAS_WIDTH * atlas_height];
+        let mut maps = rendered.into_iter().map(|glyphs| {
+            glyphs.map(|glyphs| {
+                let mut character_info = HashMap::new();
+                for glyph in glyphs {
+                    glyph.copy_into(&mut atlas);
+                    character_info.insert(glyph.c, glyph.info(atlas_height));
+                }
+                character_info
+            })
+        });
+        let character_info = maps.next().flatten().expect("The regular face is always loaded");
+        let bold = maps.next().flatten();
+        let italic = maps.next().flatten();
+        let bold_italic = maps.next().flatten();
+        drop(maps);
+
+        Ok(Font {
             character_info,
+            bold,
+            italic,
+            bold_italic,
             size: font_size,
+            atlas: Rc::new(create_atlas(display, &atlas)?),
+            atlas_cursor: cursor,
+            atlas_pixels: atlas,
+        })
+    }
+
+    /// renders `c` of the regular face into the atlas, `buffer` has to be the data the font was loaded from.
+    ///
+    /// Only the new glyph gets uploaded, unless the atlas has to grow to fit it.
+    pub fn add_glyph(&mut self, display: &impl Facade, buffer: &[u8], c: char) -> Result<(), String> {
+        if self.character_info.contains_key(&c) {
+            return Ok(());
+        }
+        let lib = ft::Library::init().map_err(|e| format!("failed to initialize freetype: {}", e))?;
+        // the font is only changed once everything that can fail succeeded, so it stays usable after an error
+        let mut cursor = self.atlas_cursor;
+        let glyph = render_glyphs(&lib, buffer, &[c], self.size, &mut cursor)
+            .map_err(|e| format!("failed to render the glyph: {}", e))?
+            .remove(0);
+
+        let atlas_height = self.atlas_pixels.len() / ATLAS_WIDTH;
+        let needed_height = cursor.y + cursor.row_height;
+        if needed_height > atlas_height {
+            // doubling the height keeps the number of uploads of the whole atlas low
+            let new_height = needed_height.max(atlas_height * 2);
+            let mut pixels = self.atlas_pixels.clone();
+            pixels.resize(ATLAS_WIDTH * new_height, 0);
+            glyph.copy_into(&mut pixels);
+            // draw calls queued before keep the old atlas, since it is shared through an `Rc`
+            self.atlas = Rc::new(create_atlas(display, &pixels)?);
+            self.atlas_pixels = pixels;
+            let scale = atlas_height as f32 / new_height as f32;
+            let maps = vec![Some(&mut self.character_info), self.bold.as_mut(), self.italic.as_mut(), self.bold_italic.as_mut()];
+            for info in maps.into_iter().flatten().flat_map(|map| map.values_mut()) {
+                info.uv_min.1 *= scale;
+                info.uv_max.1 *= scale;
+            }
+            self.character_info.insert(c, glyph.info(new_height));
+        } else {
+            glyph.copy_into(&mut self.atlas_pixels);
+            let (width, rows) = glyph.size;
+            if width > 0 && rows > 0 {
+                let image = RawImage2d {
+                    data: Cow::Borrowed(&glyph.pixels[..]),
+                    width: width as u32,
+                    height: rows as u32,
+                    format: ClientFormat::U8,
+                };
+                let (x, y) = glyph.position;
+                let rect = Rect { left: x as u32, bottom: y as u32, width: width as u32, height: rows as u32 };
+                self.atlas.write(rect, image);
+            }
+            self.character_info.insert(c, glyph.info(atlas_height));
         }
+        self.atlas_cursor = cursor;
+        Ok(())
     }
 
     pub fn get_info(&self, c: char) -> Option<&CharacterInfo> {
         self.character_info.get(&c)
     }
+
+    /// the glyph of the matching face, falls back to the regular face if the font doesn't have it
+    pub fn get_info_styled(&self, c: char, weight: FontWeight, style: FontStyle) -> Option<&CharacterInfo> {
+        let variant = match (weight, style) {
+            (FontWeight::Normal, FontStyle::Normal) => None,
+            (FontWeight::Bold, FontStyle::Normal) => self.bold.as_ref(),
+            (FontWeight::Normal, FontStyle::Italic) => self.italic.as_ref(),
+            (FontWeight::Bold, FontStyle::Italic) => self.bold_italic.as_ref().or(self.bold.as_ref()),
+        };
+        variant
+            .and_then(|character_info| character_info.get(&c))
+            .or_else(|| self.get_info(c))
+    }
+
+    pub fn measure_text(&self, text: &str) -> TextMetrics {
+        self.measure_text_styled(text, FontWeight::Normal, FontStyle::Normal)
+    }
+
+    pub fn measure_text_styled(&self, text: &str, weight: FontWeight, style: FontStyle) -> TextMetrics {
+        let advance_widths: Vec<f32> = text
+            .chars()
+            .map(|c| {
+                let info = self.get_info_styled(c, weight, style).expect("The character is missing from the font");
+                (info.advance >> 6) as f32 // bitshift by 6 to get value in pixels (2^6 = 64)
+            })
+            .collect();
+
+        TextMetrics {
+            width: advance_widths.iter().sum(),
+            height: self.size as f32,
+            advance_widths,
+        }
+    }
+}
+
+/// renders the characters of the face and reserves space for them in the atlas
+fn render_glyphs(lib: &ft::Library, data: &[u8], characters: &[char], font_size: u32, cursor: &mut AtlasCursor) -> Result<Vec<RenderedGlyph>, ft::Error> {
+    let face = lib.new_memory_face(Rc::new(data.to_vec()), 0)?;
+
+    face.set_pixel_sizes(0, font_size)?;
+
+    let mut glyphs = Vec::new();
+    for &c in characters {
+        face.load_char(c as usize, ft::face::LoadFlag::RENDER)?;
+        let glyph = face.glyph();
+        let bitmap = glyph.bitmap();
+        let (width, rows) = (bitmap.width() as usize, bitmap.rows() as usize);
+        let pitch = bitmap.pitch().abs() as usize;
+        let pixels: Vec<u8> = (0..rows)
+            .flat_map(|row| bitmap.buffer()[row * pitch..row * pitch + width].to_vec())
+            .collect();
+
+        glyphs.push(RenderedGlyph {
+            c,
+            position: cursor.place(width, rows),
+            pixels,
+            bearing: (glyph.bitmap_left(), glyph.bitmap_top()),
+            advance: glyph.advance().x,
+            size: (width, rows),
+        });
+    }
+    Ok(glyphs)
 }
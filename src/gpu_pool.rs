@@ -0,0 +1,85 @@
+use glium::{
+    index::{IndexBufferSlice, PrimitiveType},
+    vertex::VertexBufferSlice,
+    backend::Facade,
+    IndexBuffer, Vertex, VertexBuffer,
+};
+
+/// the smallest buffers that get allocated, so small draw calls don't each need their own buffers
+const MIN_CAPACITY: usize = 1024;
+
+/// vertex and index buffers taken from a `VBPool`, which only use the start of the buffers
+pub struct PooledBuffers<V: Vertex + Copy> {
+    vertex_buffer: VertexBuffer<V>,
+    index_buffer: IndexBuffer<u16>,
+    vertex_count: usize,
+    index_count: usize,
+}
+
+impl<V: Vertex + Copy> PooledBuffers<V> {
+    pub fn vertices(&self) -> VertexBufferSlice<'_, V> {
+        self.vertex_buffer.slice(0..self.vertex_count).unwrap()
+    }
+
+    /// the indices always describe a list of triangles
+    pub fn indices(&self) -> IndexBufferSlice<'_, u16> {
+        self.index_buffer.slice(0..self.index_count).unwrap()
+    }
+}
+
+/// keeps vertex and index buffers around between draw calls, so they don't have to be allocated every frame.
+///
+/// The buffers get orphaned before they are written to, so reusing a buffer that is still in use by the gpu doesn't stall.
+pub struct VBPool<V: Vertex + Copy> {
+    free: Vec<(VertexBuffer<V>, IndexBuffer<u16>)>,
+}
+
+impl<V: Vertex + Copy> VBPool<V> {
+    /// creates a pool with `count` buffers that can each hold `capacity` vertices and indices
+    pub fn with_capacity(display: &impl Facade, count: usize, capacity: usize) -> Self {
+        let free = (0..count)
+            .map(|_| Self::allocate(display, capacity, capacity))
+            .collect();
+        Self { free }
+    }
+
+    fn allocate(display: &impl Facade, vertex_capacity: usize, index_capacity: usize) -> (VertexBuffer<V>, IndexBuffer<u16>) {
+        (
+            VertexBuffer::empty_dynamic(display, vertex_capacity).unwrap(),
+            IndexBuffer::empty_dynamic(display, PrimitiveType::TrianglesList, index_capacity).unwrap(),
+        )
+    }
+
+    /// returns buffers containing the given vertices and indices, reusing a free buffer if one is big enough
+    pub fn acquire(&mut self, display: &impl Facade, vertices: &[V], indices: &[u16]) -> PooledBuffers<V> {
+        let position = self
+            .free
+            .iter()
+            .position(|(vb, ib)| vb.len() >= vertices.len() && ib.len() >= indices.len());
+        let (vertex_buffer, index_buffer) = match position {
+            Some(position) => self.free.swap_remove(position),
+            None => Self::allocate(
+                display,
+                vertices.len().next_power_of_two().max(MIN_CAPACITY),
+                indices.len().next_power_of_two().max(MIN_CAPACITY),
+            ),
+        };
+
+        vertex_buffer.invalidate();
+        vertex_buffer.slice(0..vertices.len()).unwrap().write(vertices);
+        index_buffer.invalidate();
+        index_buffer.slice(0..indices.len()).unwrap().write(indices);
+
+        PooledBuffers {
+            vertex_buffer,
+            index_buffer,
+            vertex_count: vertices.len(),
+            index_count: indices.len(),
+        }
+    }
+
+    /// gives the buffers back to the pool, so they can be reused by the next draw call
+    pub fn release(&mut self, buffers: PooledBuffers<V>) {
+        self.free.push((buffers.vertex_buffer, buffers.index_buffer));
+    }
+}
@@ -1,6 +1,6 @@
-use winit::event::VirtualKeyCode;
+use winit::event::{ModifiersState, VirtualKeyCode};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Key {
     A,
     B,
@@ -50,13 +50,58 @@ pub enum Key {
     F10,
     F11,
     F12,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadEnter,
     Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    PrintScreen,
+    ScrollLock,
+    Pause,
     Equals,
     Minus,
+    Plus,
+    Asterisk,
+    Slash,
+    Backslash,
+    Comma,
+    Period,
+    Colon,
+    Semicolon,
+    Apostrophe,
+    Grave,
+    Caret,
+    At,
+    Underline,
+    LBracket,
+    RBracket,
     Enter,
     Escape,
     Tab,
     Space,
+    CapsLock,
     LControl,
     LAlt,
     LShift,
@@ -64,7 +109,9 @@ pub enum Key {
     RControl,
     RAlt,
     RShift,
-    RWin
+    RWin,
+    /// any key that has no variant of its own
+    Unknown
 }
 
 impl Into<char> for Key {
@@ -94,7 +141,7 @@ impl Into<char> for Key {
             Self::V => 'v',
             Self::W => 'w',
             Self::X => 'x',
-            Self::Y => 'u',
+            Self::Y => 'y',
             Self::Z => 'z',
             Self::One => '1',
             Self::Two => '2',
@@ -111,6 +158,37 @@ impl Into<char> for Key {
             Self::Enter => '\n',
             Self::Equals => '=',
             Self::Minus => '-',
+            Self::Plus => '+',
+            Self::Asterisk => '*',
+            Self::Slash => '/',
+            Self::Backslash => '\\',
+            Self::Comma => ',',
+            Self::Period => '.',
+            Self::Colon => ':',
+            Self::Semicolon => ';',
+            Self::Apostrophe => '\'',
+            Self::Grave => '`',
+            Self::Caret => '^',
+            Self::At => '@',
+            Self::Underline => '_',
+            Self::LBracket => '[',
+            Self::RBracket => ']',
+            Self::Numpad0 => '0',
+            Self::Numpad1 => '1',
+            Self::Numpad2 => '2',
+            Self::Numpad3 => '3',
+            Self::Numpad4 => '4',
+            Self::Numpad5 => '5',
+            Self::Numpad6 => '6',
+            Self::Numpad7 => '7',
+            Self::Numpad8 => '8',
+            Self::Numpad9 => '9',
+            Self::NumpadAdd => '+',
+            Self::NumpadSubtract => '-',
+            Self::NumpadMultiply => '*',
+            Self::NumpadDivide => '/',
+            Self::NumpadDecimal => '.',
+            Self::NumpadEnter => '\n',
             Self::F1 
             | Self::F2
             | Self::F3
@@ -124,6 +202,20 @@ impl Into<char> for Key {
             | Self::F11
             | Self::F12
             | Self::Backspace
+            | Self::Delete
+            | Self::Left
+            | Self::Right
+            | Self::Up
+            | Self::Down
+            | Self::Home
+            | Self::End
+            | Self::PageUp
+            | Self::PageDown
+            | Self::Insert
+            | Self::PrintScreen
+            | Self::ScrollLock
+            | Self::Pause
+            | Self::CapsLock
             | Self::Escape
             | Self::LControl
             | Self::LAlt
@@ -132,11 +224,111 @@ impl Into<char> for Key {
             | Self::RControl
             | Self::RAlt
             | Self::RShift
-            | Self::RWin => 0 as char,
+            | Self::RWin
+            | Self::Unknown => 0 as char,
         }
     }
 }
 
+impl Key {
+    /// true for keys that type a character, as opposed to keys used for navigation or as modifiers
+    pub fn is_printable(&self) -> bool {
+        let c: char = (*self).into();
+        !c.is_control()
+    }
+
+    /// true for the control, alt, shift and windows keys on either side of the keyboard
+    pub fn is_modifier(&self) -> bool {
+        matches!(
+            self,
+            Self::LControl | Self::RControl | Self::LAlt | Self::RAlt | Self::LShift | Self::RShift | Self::LWin | Self::RWin
+        )
+    }
+
+    /// true for the keys that move a cursor, e.g. the arrow keys
+    pub fn is_navigation(&self) -> bool {
+        matches!(
+            self,
+            Self::Left | Self::Right | Self::Up | Self::Down | Self::Home | Self::End | Self::PageUp | Self::PageDown
+        )
+    }
+
+    /// true for F1 to F12
+    pub fn is_function_key(&self) -> bool {
+        matches!(
+            self,
+            Self::F1 | Self::F2 | Self::F3 | Self::F4 | Self::F5 | Self::F6 | Self::F7 | Self::F8 | Self::F9 | Self::F10 | Self::F11 | Self::F12
+        )
+    }
+
+    /// the key that types `c`, `None` if there is no such key.
+    ///
+    /// Upper case letters map to the same key as their lower case version and
+    /// characters that can also be typed on the numpad map to the main keyboard.
+    pub fn try_from(c: char) -> Option<Key> {
+        let key = match c.to_ascii_lowercase() {
+            'a' => Self::A,
+            'b' => Self::B,
+            'c' => Self::C,
+            'd' => Self::D,
+            'e' => Self::E,
+            'f' => Self::F,
+            'g' => Self::G,
+            'h' => Self::H,
+            'i' => Self::I,
+            'j' => Self::J,
+            'k' => Self::K,
+            'l' => Self::L,
+            'm' => Self::M,
+            'n' => Self::N,
+            'o' => Self::O,
+            'p' => Self::P,
+            'q' => Self::Q,
+            'r' => Self::R,
+            's' => Self::S,
+            't' => Self::T,
+            'u' => Self::U,
+            'v' => Self::V,
+            'w' => Self::W,
+            'x' => Self::X,
+            'y' => Self::Y,
+            'z' => Self::Z,
+            '1' => Self::One,
+            '2' => Self::Two,
+            '3' => Self::Three,
+            '4' => Self::Four,
+            '5' => Self::Five,
+            '6' => Self::Six,
+            '7' => Self::Seven,
+            '8' => Self::Eight,
+            '9' => Self::Nine,
+            '0' => Self::Zero,
+            ' ' => Self::Space,
+            '\t' => Self::Tab,
+            '\n' => Self::Enter,
+            '=' => Self::Equals,
+            '-' => Self::Minus,
+            '+' => Self::Plus,
+            '*' => Self::Asterisk,
+            '/' => Self::Slash,
+            '\\' => Self::Backslash,
+            ',' => Self::Comma,
+            '.' => Self::Period,
+            ':' => Self::Colon,
+            ';' => Self::Semicolon,
+            '\'' => Self::Apostrophe,
+            '`' => Self::Grave,
+            '^' => Self::Caret,
+            '@' => Self::At,
+            '_' => Self::Underline,
+            '[' => Self::LBracket,
+            ']' => Self::RBracket,
+            _ => return None,
+        };
+        Some(key)
+    }
+}
+
 impl From<VirtualKeyCode> for Key {
     fn from(key: VirtualKeyCode) -> Self {
         match key {
@@ -188,13 +380,58 @@ impl From<VirtualKeyCode> for Key {
            VirtualKeyCode::F10 => Self::F10,
            VirtualKeyCode::F11 => Self::F11,
            VirtualKeyCode::F12 => Self::F12,
+           VirtualKeyCode::Numpad0 => Self::Numpad0,
+           VirtualKeyCode::Numpad1 => Self::Numpad1,
+           VirtualKeyCode::Numpad2 => Self::Numpad2,
+           VirtualKeyCode::Numpad3 => Self::Numpad3,
+           VirtualKeyCode::Numpad4 => Self::Numpad4,
+           VirtualKeyCode::Numpad5 => Self::Numpad5,
+           VirtualKeyCode::Numpad6 => Self::Numpad6,
+           VirtualKeyCode::Numpad7 => Self::Numpad7,
+           VirtualKeyCode::Numpad8 => Self::Numpad8,
+           VirtualKeyCode::Numpad9 => Self::Numpad9,
+           VirtualKeyCode::NumpadAdd => Self::NumpadAdd,
+           VirtualKeyCode::NumpadSubtract => Self::NumpadSubtract,
+           VirtualKeyCode::NumpadMultiply => Self::NumpadMultiply,
+           VirtualKeyCode::NumpadDivide => Self::NumpadDivide,
+           VirtualKeyCode::NumpadDecimal => Self::NumpadDecimal,
+           VirtualKeyCode::NumpadEnter => Self::NumpadEnter,
            VirtualKeyCode::Back => Self::Backspace,
+           VirtualKeyCode::Delete => Self::Delete,
+           VirtualKeyCode::Left => Self::Left,
+           VirtualKeyCode::Right => Self::Right,
+           VirtualKeyCode::Up => Self::Up,
+           VirtualKeyCode::Down => Self::Down,
+           VirtualKeyCode::Home => Self::Home,
+           VirtualKeyCode::End => Self::End,
+           VirtualKeyCode::PageUp => Self::PageUp,
+           VirtualKeyCode::PageDown => Self::PageDown,
+           VirtualKeyCode::Insert => Self::Insert,
+           VirtualKeyCode::Snapshot => Self::PrintScreen,
+           VirtualKeyCode::Scroll => Self::ScrollLock,
+           VirtualKeyCode::Pause => Self::Pause,
            VirtualKeyCode::Return => Self::Enter,
            VirtualKeyCode::Escape => Self::Escape,
            VirtualKeyCode::Tab => Self::Tab,
            VirtualKeyCode::Space => Self::Space,
            VirtualKeyCode::Equals => Self::Equals,
            VirtualKeyCode::Minus => Self::Minus,
+           VirtualKeyCode::Plus => Self::Plus,
+           VirtualKeyCode::Asterisk => Self::Asterisk,
+           VirtualKeyCode::Slash => Self::Slash,
+           VirtualKeyCode::Backslash => Self::Backslash,
+           VirtualKeyCode::Comma => Self::Comma,
+           VirtualKeyCode::Period => Self::Period,
+           VirtualKeyCode::Colon => Self::Colon,
+           VirtualKeyCode::Semicolon => Self::Semicolon,
+           VirtualKeyCode::Apostrophe => Self::Apostrophe,
+           VirtualKeyCode::Grave => Self::Grave,
+           VirtualKeyCode::Caret => Self::Caret,
+           VirtualKeyCode::At => Self::At,
+           VirtualKeyCode::Underline => Self::Underline,
+           VirtualKeyCode::LBracket => Self::LBracket,
+           VirtualKeyCode::RBracket => Self::RBracket,
+           VirtualKeyCode::Capital => Self::CapsLock,
            VirtualKeyCode::LControl => Self::LControl,
            VirtualKeyCode::LAlt => Self::LAlt,
            VirtualKeyCode::LShift => Self::LShift,
@@ -203,7 +440,369 @@ impl From<VirtualKeyCode> for Key {
            VirtualKeyCode::RAlt => Self::RAlt,
            VirtualKeyCode::RShift => Self::RShift,
            VirtualKeyCode::RWin => Self::RWin,
-            x => todo!("{:?}", x)
+           _ => Self::Unknown
+        }
+    }
+}
+
+/// a pressed key, `is_repeat` is true for the presses the os generates while the key is held
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct KeyEvent {
+    pub key: Key,
+    pub is_repeat: bool
+}
+
+/// a key together with the modifiers that have to be held while pressing it, built through chained calls:
+///
+/// `KeyCombo::new(Key::Z).ctrl().shift()`
+///
+/// Additional modifiers may be held as well, unless the combo is `exact`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct KeyCombo {
+    pub key: Key,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub logo: bool,
+    /// only match while no other modifiers are held, e.g. so `Ctrl+Z` doesn't also fire for `Ctrl+Shift+Z`
+    pub exact: bool
+}
+
+impl KeyCombo {
+    pub fn new(key: Key) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+            logo: false,
+            exact: false
+        }
+    }
+
+    pub fn ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    pub fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    pub fn alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    /// the windows key on windows and the command key on macos
+    pub fn logo(mut self) -> Self {
+        self.logo = true;
+        self
+    }
+
+    pub fn exact(mut self) -> Self {
+        self.exact = true;
+        self
+    }
+
+    /// a human readable version of the combo like `Ctrl+Shift+Z`
+    pub fn display_string(&self) -> String {
+        let mut parts = Vec::new();
+        if self.ctrl {
+            parts.push("Ctrl".to_string());
+        }
+        if self.shift {
+            parts.push("Shift".to_string());
+        }
+        if self.alt {
+            parts.push("Alt".to_string());
+        }
+        if self.logo {
+            parts.push("Super".to_string());
+        }
+        let c: char = self.key.into();
+        parts.push(if self.key.is_printable() && self.key != Key::Space {
+            c.to_ascii_uppercase().to_string()
+        } else {
+            format!("{:?}", self.key)
+        });
+        parts.join("+")
+    }
+
+    /// returns true if the key is in `keys` and all of the required modifiers are held, or exactly those if the combo is `exact`
+    pub fn matches(&self, keys: &[Key], mods: ModifiersState) -> bool {
+        let modifier_matches = |required: bool, held: bool| if self.exact { required == held } else { !required || held };
+        keys.contains(&self.key)
+            && modifier_matches(self.ctrl, mods.ctrl())
+            && modifier_matches(self.shift, mods.shift())
+            && modifier_matches(self.alt, mods.alt())
+            && modifier_matches(self.logo, mods.logo())
+    }
+}
+
+impl Into<KeyCombo> for Key {
+    fn into(self) -> KeyCombo {
+        KeyCombo::new(self)
+    }
+}
+
+/// a key together with exactly the modifiers that have to be held while pressing it, see `KeyCombo` for more control
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct KeyBinding {
+    pub key: Key,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool
+}
+
+impl KeyBinding {
+    pub fn new(key: Key) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false
+        }
+    }
+
+    pub fn ctrl(key: Key) -> Self {
+        Self {
+            ctrl: true,
+            ..Self::new(key)
+        }
+    }
+
+    pub fn shift(key: Key) -> Self {
+        Self {
+            shift: true,
+            ..Self::new(key)
+        }
+    }
+
+    pub fn alt(key: Key) -> Self {
+        Self {
+            alt: true,
+            ..Self::new(key)
+        }
+    }
+
+    pub fn ctrl_shift(key: Key) -> Self {
+        Self {
+            ctrl: true,
+            shift: true,
+            ..Self::new(key)
+        }
+    }
+}
+
+impl Into<KeyBinding> for Key {
+    fn into(self) -> KeyBinding {
+        KeyBinding::new(self)
+    }
+}
+
+impl Into<KeyCombo> for KeyBinding {
+    fn into(self) -> KeyCombo {
+        KeyCombo {
+            key: self.key,
+            ctrl: self.ctrl,
+            shift: self.shift,
+            alt: self.alt,
+            logo: false,
+            exact: false
+        }
+        .exact()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Key, KeyBinding, KeyCombo};
+    use winit::event::{ModifiersState, VirtualKeyCode};
+
+    #[test]
+    fn combos_match_at_least_their_modifiers_unless_exact() {
+        let ctrl_shift = ModifiersState::CTRL | ModifiersState::SHIFT;
+        assert!(KeyCombo::new(Key::Z).ctrl().matches(&[Key::Z], ctrl_shift));
+        assert!(!KeyCombo::new(Key::Z).ctrl().exact().matches(&[Key::Z], ctrl_shift));
+        assert!(KeyCombo::new(Key::Z).ctrl().shift().exact().matches(&[Key::Z], ctrl_shift));
+        assert!(!KeyCombo::new(Key::Z).ctrl().shift().matches(&[Key::Z], ModifiersState::CTRL));
+        assert!(!KeyCombo::new(Key::Z).matches(&[Key::Y], ModifiersState::empty()));
+        let binding: KeyCombo = KeyBinding::ctrl(Key::Z).into();
+        assert!(!binding.matches(&[Key::Z], ctrl_shift));
+    }
+
+    #[test]
+    fn every_virtual_key_code_maps_to_its_key() {
+        // every variant of `VirtualKeyCode`, keys without a counterpart map to `Key::Unknown`
+        let expected = [
+            (VirtualKeyCode::Key1, Key::One),
+            (VirtualKeyCode::Key2, Key::Two),
+            (VirtualKeyCode::Key3, Key::Three),
+            (VirtualKeyCode::Key4, Key::Four),
+            (VirtualKeyCode::Key5, Key::Five),
+            (VirtualKeyCode::Key6, Key::Six),
+            (VirtualKeyCode::Key7, Key::Seven),
+            (VirtualKeyCode::Key8, Key::Eight),
+            (VirtualKeyCode::Key9, Key::Nine),
+            (VirtualKeyCode::Key0, Key::Zero),
+            (VirtualKeyCode::A, Key::A),
+            (VirtualKeyCode::B, Key::B),
+            (VirtualKeyCode::C, Key::C),
+            (VirtualKeyCode::D, Key::D),
+            (VirtualKeyCode::E, Key::E),
+            (VirtualKeyCode::F, Key::F),
+            (VirtualKeyCode::G, Key::G),
+            (VirtualKeyCode::H, Key::H),
+            (VirtualKeyCode::I, Key::I),
+            (VirtualKeyCode::J, Key::J),
+            (VirtualKeyCode::K, Key::K),
+            (VirtualKeyCode::L, Key::L),
+            (VirtualKeyCode::M, Key::M),
+            (VirtualKeyCode::N, Key::N),
+            (VirtualKeyCode::O, Key::O),
+            (VirtualKeyCode::P, Key::P),
+            (VirtualKeyCode::Q, Key::Q),
+            (VirtualKeyCode::R, Key::R),
+            (VirtualKeyCode::S, Key::S),
+            (VirtualKeyCode::T, Key::T),
+            (VirtualKeyCode::U, Key::U),
+            (VirtualKeyCode::V, Key::V),
+            (VirtualKeyCode::W, Key::W),
+            (VirtualKeyCode::X, Key::X),
+            (VirtualKeyCode::Y, Key::Y),
+            (VirtualKeyCode::Z, Key::Z),
+            (VirtualKeyCode::Escape, Key::Escape),
+            (VirtualKeyCode::F1, Key::F1),
+            (VirtualKeyCode::F2, Key::F2),
+            (VirtualKeyCode::F3, Key::F3),
+            (VirtualKeyCode::F4, Key::F4),
+            (VirtualKeyCode::F5, Key::F5),
+            (VirtualKeyCode::F6, Key::F6),
+            (VirtualKeyCode::F7, Key::F7),
+            (VirtualKeyCode::F8, Key::F8),
+            (VirtualKeyCode::F9, Key::F9),
+            (VirtualKeyCode::F10, Key::F10),
+            (VirtualKeyCode::F11, Key::F11),
+            (VirtualKeyCode::F12, Key::F12),
+            (VirtualKeyCode::F13, Key::Unknown),
+            (VirtualKeyCode::F14, Key::Unknown),
+            (VirtualKeyCode::F15, Key::Unknown),
+            (VirtualKeyCode::F16, Key::Unknown),
+            (VirtualKeyCode::F17, Key::Unknown),
+            (VirtualKeyCode::F18, Key::Unknown),
+            (VirtualKeyCode::F19, Key::Unknown),
+            (VirtualKeyCode::F20, Key::Unknown),
+            (VirtualKeyCode::F21, Key::Unknown),
+            (VirtualKeyCode::F22, Key::Unknown),
+            (VirtualKeyCode::F23, Key::Unknown),
+            (VirtualKeyCode::F24, Key::Unknown),
+            (VirtualKeyCode::Snapshot, Key::PrintScreen),
+            (VirtualKeyCode::Scroll, Key::ScrollLock),
+            (VirtualKeyCode::Pause, Key::Pause),
+            (VirtualKeyCode::Insert, Key::Insert),
+            (VirtualKeyCode::Home, Key::Home),
+            (VirtualKeyCode::Delete, Key::Delete),
+            (VirtualKeyCode::End, Key::End),
+            (VirtualKeyCode::PageDown, Key::PageDown),
+            (VirtualKeyCode::PageUp, Key::PageUp),
+            (VirtualKeyCode::Left, Key::Left),
+            (VirtualKeyCode::Up, Key::Up),
+            (VirtualKeyCode::Right, Key::Right),
+            (VirtualKeyCode::Down, Key::Down),
+            (VirtualKeyCode::Back, Key::Backspace),
+            (VirtualKeyCode::Return, Key::Enter),
+            (VirtualKeyCode::Space, Key::Space),
+            (VirtualKeyCode::Compose, Key::Unknown),
+            (VirtualKeyCode::Caret, Key::Caret),
+            (VirtualKeyCode::Numlock, Key::Unknown),
+            (VirtualKeyCode::Numpad0, Key::Numpad0),
+            (VirtualKeyCode::Numpad1, Key::Numpad1),
+            (VirtualKeyCode::Numpad2, Key::Numpad2),
+            (VirtualKeyCode::Numpad3, Key::Numpad3),
+            (VirtualKeyCode::Numpad4, Key::Numpad4),
+            (VirtualKeyCode::Numpad5, Key::Numpad5),
+            (VirtualKeyCode::Numpad6, Key::Numpad6),
+            (VirtualKeyCode::Numpad7, Key::Numpad7),
+            (VirtualKeyCode::Numpad8, Key::Numpad8),
+            (VirtualKeyCode::Numpad9, Key::Numpad9),
+            (VirtualKeyCode::NumpadAdd, Key::NumpadAdd),
+            (VirtualKeyCode::NumpadDivide, Key::NumpadDivide),
+            (VirtualKeyCode::NumpadDecimal, Key::NumpadDecimal),
+            (VirtualKeyCode::NumpadComma, Key::Unknown),
+            (VirtualKeyCode::NumpadEnter, Key::NumpadEnter),
+            (VirtualKeyCode::NumpadEquals, Key::Unknown),
+            (VirtualKeyCode::NumpadMultiply, Key::NumpadMultiply),
+            (VirtualKeyCode::NumpadSubtract, Key::NumpadSubtract),
+            (VirtualKeyCode::AbntC1, Key::Unknown),
+            (VirtualKeyCode::AbntC2, Key::Unknown),
+            (VirtualKeyCode::Apostrophe, Key::Apostrophe),
+            (VirtualKeyCode::Apps, Key::Unknown),
+            (VirtualKeyCode::Asterisk, Key::Asterisk),
+            (VirtualKeyCode::At, Key::At),
+            (VirtualKeyCode::Ax, Key::Unknown),
+            (VirtualKeyCode::Backslash, Key::Backslash),
+            (VirtualKeyCode::Calculator, Key::Unknown),
+            (VirtualKeyCode::Capital, Key::CapsLock),
+            (VirtualKeyCode::Colon, Key::Colon),
+            (VirtualKeyCode::Comma, Key::Comma),
+            (VirtualKeyCode::Convert, Key::Unknown),
+            (VirtualKeyCode::Equals, Key::Equals),
+            (VirtualKeyCode::Grave, Key::Grave),
+            (VirtualKeyCode::Kana, Key::Unknown),
+            (VirtualKeyCode::Kanji, Key::Unknown),
+            (VirtualKeyCode::LAlt, Key::LAlt),
+            (VirtualKeyCode::LBracket, Key::LBracket),
+            (VirtualKeyCode::LControl, Key::LControl),
+            (VirtualKeyCode::LShift, Key::LShift),
+            (VirtualKeyCode::LWin, Key::LWin),
+            (VirtualKeyCode::Mail, Key::Unknown),
+            (VirtualKeyCode::MediaSelect, Key::Unknown),
+            (VirtualKeyCode::MediaStop, Key::Unknown),
+            (VirtualKeyCode::Minus, Key::Minus),
+            (VirtualKeyCode::Mute, Key::Unknown),
+            (VirtualKeyCode::MyComputer, Key::Unknown),
+            (VirtualKeyCode::NavigateForward, Key::Unknown),
+            (VirtualKeyCode::NavigateBackward, Key::Unknown),
+            (VirtualKeyCode::NextTrack, Key::Unknown),
+            (VirtualKeyCode::NoConvert, Key::Unknown),
+            (VirtualKeyCode::OEM102, Key::Unknown),
+            (VirtualKeyCode::Period, Key::Period),
+            (VirtualKeyCode::PlayPause, Key::Unknown),
+            (VirtualKeyCode::Plus, Key::Plus),
+            (VirtualKeyCode::Power, Key::Unknown),
+            (VirtualKeyCode::PrevTrack, Key::Unknown),
+            (VirtualKeyCode::RAlt, Key::RAlt),
+            (VirtualKeyCode::RBracket, Key::RBracket),
+            (VirtualKeyCode::RControl, Key::RControl),
+            (VirtualKeyCode::RShift, Key::RShift),
+            (VirtualKeyCode::RWin, Key::RWin),
+            (VirtualKeyCode::Semicolon, Key::Semicolon),
+            (VirtualKeyCode::Slash, Key::Slash),
+            (VirtualKeyCode::Sleep, Key::Unknown),
+            (VirtualKeyCode::Stop, Key::Unknown),
+            (VirtualKeyCode::Sysrq, Key::Unknown),
+            (VirtualKeyCode::Tab, Key::Tab),
+            (VirtualKeyCode::Underline, Key::Underline),
+            (VirtualKeyCode::Unlabeled, Key::Unknown),
+            (VirtualKeyCode::VolumeDown, Key::Unknown),
+            (VirtualKeyCode::VolumeUp, Key::Unknown),
+            (VirtualKeyCode::Wake, Key::Unknown),
+            (VirtualKeyCode::WebBack, Key::Unknown),
+            (VirtualKeyCode::WebFavorites, Key::Unknown),
+            (VirtualKeyCode::WebForward, Key::Unknown),
+            (VirtualKeyCode::WebHome, Key::Unknown),
+            (VirtualKeyCode::WebRefresh, Key::Unknown),
+            (VirtualKeyCode::WebSearch, Key::Unknown),
+            (VirtualKeyCode::WebStop, Key::Unknown),
+            (VirtualKeyCode::Yen, Key::Unknown),
+            (VirtualKeyCode::Copy, Key::Unknown),
+            (VirtualKeyCode::Paste, Key::Unknown),
+            (VirtualKeyCode::Cut, Key::Unknown),
+        ];
+        for (code, key) in expected.iter() {
+            assert_eq!(Key::from(*code), *key, "{:?}", code);
         }
     }
 }
@@ -8,21 +8,28 @@ use winit::{
 use chrono::prelude::*;
 
 use crate::application::ApplicationWrapper;
-use crate::renderer::Renderer;
+use crate::renderer::{MouseButton, Renderer};
 use crate::key::Key;
 use crate::primitives::{TextInputStyle, TextInputState};
+use crate::styling::Padding;
+use crate::window::WindowConfig;
 
 mod animation;
 mod appbar;
 mod application;
+mod backend;
 mod key;
 mod color;
+mod error;
 mod font;
+mod gpu_pool;
 mod renderer;
 mod shaders;
 mod vertex;
 mod primitives;
 mod styling;
+mod theme;
+mod window;
 
 use application::Application;
 use color::Color;
@@ -42,12 +49,12 @@ impl App {
 impl Application for App {
     fn on_mouse_down(
         &mut self,
-        left: bool,
+        button: MouseButton,
         x: f32,
         y: f32,
         r: &mut Renderer
     ) -> Option<ControlFlow> {
-        if left {
+        if button == MouseButton::Left {
             r.active_id = r.get_hit(x, y);
         }
         None
@@ -59,6 +66,9 @@ impl Application for App {
         r: &mut Renderer,
     ) -> Option<ControlFlow> {
         match key {
+            Key::F1 if r.is_shift() => {
+                r.change_font_size(40);
+            },
             Key::F1 => {
                 r.change_font_size(30);
             },
@@ -75,14 +85,20 @@ impl Application for App {
         None
     }
 
+    fn window_config(&self) -> WindowConfig {
+        WindowConfig::new().title("cimir")
+    }
+
     fn init(&mut self, r: &mut Renderer) {
         r.set_background_color(Color::new(230, 230, 230));
     }
 
     fn render(&mut self, r: &mut Renderer) {
         r.set_cursor(10.0, 10.0, |r| {
-            r.text_input(0, &mut self.state, CustomStyle);
-            r.text_input(1, &mut self.state, CustomStyle);
+            let first = r.next_id();
+            r.text_input(first, &mut self.state, CustomStyle);
+            let second = r.next_id();
+            r.text_input(second, &mut self.state, CustomStyle);
             r.space(1.0);
             for c in self.state.value.chars() {
                 r.space(1.0);
@@ -99,7 +115,7 @@ struct CustomStyle;
 impl Into<TextInputStyle> for CustomStyle {
     fn into(self) -> TextInputStyle {
         TextInputStyle {
-            padding: (5.0, 3.0).into(),
+            padding: Padding::symmetric(5.0, 3.0),
             min_width: 200.0,
             foreground_color: Color::BLACK,
             background_color: Some(Color::new(180, 180, 180)),
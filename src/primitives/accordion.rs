@@ -0,0 +1,92 @@
+use crate::animation::Transition;
+use crate::styling::Padding;
+use crate::renderer::{CursorIcon, Renderer, Triangles};
+use crate::primitives::LabelStyle;
+use crate::vertex::Vertex;
+
+use std::time::Duration;
+
+/// the width and height of the toggle arrow
+pub(crate) const ARROW_SIZE: f32 = 8.0;
+/// the space around the title of the header
+const HEADER_PADDING: f32 = 4.0;
+const EXPAND_DURATION: Duration = Duration::from_millis(200);
+
+#[derive(Default)]
+struct AccordionState {
+    was_open: Option<bool>,
+    /// the body only gets animated once the accordion has been toggled
+    toggled: bool,
+}
+
+impl Renderer {
+    /// draws a header which toggles `open` when clicked and the body rendered by `f` below it while `open` is true.
+    pub fn accordion(&mut self, id: u32, title: &str, open: &mut bool, mut f: impl FnMut(&mut Self)) {
+        let animation_id = self.id_from_str(&format!("{} accordion", id));
+
+        self.col(|r| {
+            r.hitbox(id, |r, hot, _active, _focused| {
+                if hot {
+                    r.set_cursor_icon(CursorIcon::Hand);
+                }
+                let (x, y) = r.pos();
+                let (_, height, _) = r.label(title, LabelStyle {
+                    background_color: Some(if hot { r.theme().hover_color } else { r.theme().control_color }),
+                    padding: Padding::sides(2.0 * HEADER_PADDING + ARROW_SIZE, HEADER_PADDING, HEADER_PADDING, HEADER_PADDING),
+                    ..Default::default()
+                });
+                r.draw_arrow(x + HEADER_PADDING, y + height / 2.0, *open);
+            });
+            if r.clicked(id) {
+                *open = !*open;
+            }
+
+            let state = r.get_state::<AccordionState>(id);
+            if state.was_open.replace(*open).map(|was_open| was_open != *open).unwrap_or(false) {
+                state.toggled = true;
+                r.reset_animation(animation_id);
+            }
+
+            let mut progress = 1.0;
+            if r.get_state::<AccordionState>(id).toggled {
+                r.animate(animation_id, EXPAND_DURATION, &[Transition::EaseInOut(0.0, 1.0)], |_, [p]| {
+                    progress = p;
+                });
+            }
+            let visible = if *open { progress } else { 1.0 - progress };
+
+            if visible >= 1.0 {
+                f(r);
+            } else if visible > 0.0 {
+                // the body is clipped to the part that has already expanded
+                let (width, height) = r.measure_layout(|r| f(r));
+                let (x, y) = r.pos();
+                r.begin_clip(x, y, width, height * visible);
+                r.set_cursor_detached(x, y, |r| r.col(|r| f(r)));
+                r.end_clip();
+                r.handle_new_shape(width, height * visible);
+            }
+        });
+    }
+
+    /// draws a triangle pointing down when `open` is true and to the right otherwise
+    pub(crate) fn draw_arrow(&mut self, x: f32, center_y: f32, open: bool) {
+        let half = ARROW_SIZE / 2.0;
+        let quarter = ARROW_SIZE / 4.0;
+        let color = self.theme().foreground_color;
+        let vertices = if open {
+            [
+                Vertex::colored(color, x, center_y - quarter),
+                Vertex::colored(color, x + ARROW_SIZE, center_y - quarter),
+                Vertex::colored(color, x + half, center_y + quarter),
+            ]
+        } else {
+            [
+                Vertex::colored(color, x + quarter, center_y - half),
+                Vertex::colored(color, x + quarter, center_y + half),
+                Vertex::colored(color, x + ARROW_SIZE - quarter, center_y),
+            ]
+        };
+        self.draw_primitive(&vertices, Triangles::List);
+    }
+}
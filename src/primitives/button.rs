@@ -0,0 +1,68 @@
+use crate::color::Color;
+use crate::styling::{Border, FocusStyle, InheritedStyle, Margin, Padding};
+use crate::theme::Theme;
+use crate::renderer::{CursorIcon, Renderer};
+use crate::primitives::LabelStyle;
+
+#[derive(Debug, Copy, Clone)]
+pub struct ButtonStyle {
+    pub background_color: Color,
+    pub hover_color: Color,
+    pub foreground_color: Color,
+    pub padding: Padding,
+    pub margin: Margin,
+    pub min_width: f32,
+    pub border_radius: f32,
+    pub border: Option<Border>,
+    pub focus: FocusStyle
+}
+
+impl Default for ButtonStyle {
+    fn default() -> Self {
+        let theme = Theme::current();
+        Self {
+            background_color: theme.control_color,
+            hover_color: theme.hover_color,
+            foreground_color: InheritedStyle::current().foreground_color.unwrap_or(theme.foreground_color),
+            padding: Padding::symmetric(4.0, 8.0),
+            margin: 0.0.into(),
+            min_width: 0.0,
+            border_radius: 0.0,
+            border: None,
+            focus: Default::default()
+        }
+    }
+}
+
+impl Into<ButtonStyle> for () {
+    fn into(self) -> ButtonStyle {
+        Default::default()
+    }
+}
+
+impl Renderer {
+    /// returns true if the button was clicked this frame
+    pub fn button<T: Into<ButtonStyle>>(&mut self, id: u32, text: &str, style: T) -> bool {
+        let style = style.into();
+        self.hitbox(id, |r, hot, _active, focused| {
+            if hot {
+                r.set_cursor_icon(CursorIcon::Hand);
+            }
+            r.label(text, LabelStyle {
+                background_color: Some(if hot { style.hover_color } else { style.background_color }),
+                foreground_color: style.foreground_color,
+                padding: style.padding,
+                margin: style.margin,
+                min_width: style.min_width,
+                border_radius: style.border_radius,
+                border: style.border,
+                ..Default::default()
+            });
+            if focused {
+                r.draw_focus_ring(style.focus.color, style.focus.thickness);
+            }
+        });
+
+        self.clicked(id)
+    }
+}
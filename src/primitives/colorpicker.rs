@@ -0,0 +1,100 @@
+use crate::color::Color;
+use crate::styling::Padding;
+use crate::renderer::{Renderer, OVERLAY_LAYER};
+use crate::primitives::{ButtonStyle, SliderStyle};
+
+#[derive(Debug, Copy, Clone)]
+pub struct ColorPickerStyle {
+    /// the width and height of the preview of the current color
+    pub swatch_size: f32,
+    /// the space between the border of the popup and the sliders
+    pub padding: f32,
+    pub slider: SliderStyle
+}
+
+impl Default for ColorPickerStyle {
+    fn default() -> Self {
+        Self {
+            swatch_size: 20.0,
+            padding: 8.0,
+            slider: Default::default()
+        }
+    }
+}
+
+impl Into<ColorPickerStyle> for () {
+    fn into(self) -> ColorPickerStyle {
+        Default::default()
+    }
+}
+
+#[derive(Default)]
+struct ColorPickerState {
+    open: bool
+}
+
+impl Renderer {
+    /// draws a swatch of `color` and a button which opens a popup with sliders for the red, green and blue channel.
+    ///
+    /// Returns true if the color changed this frame.
+    pub fn color_picker<T: Into<ColorPickerStyle>>(&mut self, id: u32, color: &mut Color, style: T) -> bool {
+        let style = style.into();
+        let previous = *color;
+        let panel_id = self.id_from_str(&format!("{} color picker panel", id));
+        let slider_ids: Vec<u32> = ["red", "green", "blue"]
+            .iter()
+            .map(|channel| self.id_from_str(&format!("{} color picker {}", id, channel)))
+            .collect();
+        let mut open = self.get_state::<ColorPickerState>(id).open;
+
+        let (x, y) = self.pos();
+        self.row(|r| {
+            r.rectangle((style.swatch_size, style.swatch_size), *color);
+            r.space(4.0);
+            if r.button(id, "...", ButtonStyle { padding: Padding::symmetric(4.0, 1.0), ..Default::default() }) {
+                open = !open;
+            }
+        });
+
+        if open {
+            let mut channels = [color.r() as f32, color.g() as f32, color.b() as f32];
+            let mut sliders = |r: &mut Self| {
+                r.col(|r| {
+                    for (i, name) in ["R", "G", "B"].iter().enumerate() {
+                        r.row(|r| {
+                            r.text(name, r.theme().foreground_color);
+                            r.space(4.0);
+                            r.slider(slider_ids[i], &mut channels[i], 0.0, 255.0, style.slider);
+                        });
+                        r.space(4.0);
+                    }
+                });
+            };
+            let (width, height) = self.measure_layout(|r| sliders(r));
+            let panel_y = y + style.swatch_size + 4.0;
+            self.with_layer(OVERLAY_LAYER, |r| {
+                r.set_cursor_detached(x, panel_y, |r| {
+                    r.hitbox(panel_id, |r, _, _, _| {
+                        r.rectangle((width + 2.0 * style.padding, height + 2.0 * style.padding), r.theme().surface_color);
+                    });
+                });
+                r.set_cursor_detached(x + style.padding, panel_y + style.padding, |r| sliders(r));
+            });
+            *color = Color::rgba(
+                channels[0].round() as u16,
+                channels[1].round() as u16,
+                channels[2].round() as u16,
+                color.a()
+            );
+
+            // clicking anywhere outside of the popup closes it
+            let inside = self.hot_id.map(|hot| hot == id || hot == panel_id || slider_ids.contains(&hot)).unwrap_or(false);
+            if self.mouse.lmouseclick && !inside {
+                open = false;
+            }
+        }
+        self.get_state::<ColorPickerState>(id).open = open;
+
+        *color != previous
+    }
+}
@@ -0,0 +1,99 @@
+use crate::color::Color;
+use crate::styling::Padding;
+use crate::theme::Theme;
+use crate::renderer::{Renderer, OVERLAY_LAYER};
+use crate::primitives::ButtonStyle;
+
+#[derive(Debug, Copy, Clone)]
+pub struct ContextMenuStyle {
+    pub background_color: Color,
+    pub hover_color: Color,
+    pub foreground_color: Color,
+    pub padding: Padding,
+    /// every item is at least this wide, so the hover highlight spans the whole menu
+    pub min_width: f32
+}
+
+impl Default for ContextMenuStyle {
+    fn default() -> Self {
+        let theme = Theme::current();
+        Self {
+            background_color: theme.surface_color,
+            hover_color: theme.hover_color,
+            foreground_color: theme.foreground_color,
+            padding: Padding::symmetric(4.0, 8.0),
+            min_width: 120.0
+        }
+    }
+}
+
+impl Into<ContextMenuStyle> for () {
+    fn into(self) -> ContextMenuStyle {
+        Default::default()
+    }
+}
+
+/// where the menu was opened, `None` while it is closed
+#[derive(Debug, Default)]
+struct ContextMenuState {
+    position: Option<(f32, f32)>
+}
+
+impl Renderer {
+    /// opens a menu at the mouse when the widget `trigger_id` gets right-clicked.
+    ///
+    /// Clicking an item calls its function, any click closes the menu again.
+    pub fn context_menu<T: Into<ContextMenuStyle>>(
+        &mut self,
+        trigger_id: u32,
+        items: &[(&str, impl Fn(&mut Self))],
+        style: T
+    ) {
+        let style = style.into();
+        let state_id = self.id_from_str(&format!("{} context menu", trigger_id));
+        let item_ids: Vec<u32> = (0..items.len())
+            .map(|i| self.id_from_str(&format!("{} context menu item {}", trigger_id, i)))
+            .collect();
+
+        let mut position = self.get_state::<ContextMenuState>(state_id).position;
+        if self.is_hot(trigger_id) && self.mouse.rmouseclick {
+            position = Some((self.mouse.x as f32, self.mouse.y as f32));
+        } else if self.mouse.rmouseclick {
+            position = None;
+        }
+
+        if let Some((x, y)) = position {
+            let item_style = ButtonStyle {
+                background_color: style.background_color,
+                hover_color: style.hover_color,
+                foreground_color: style.foreground_color,
+                padding: style.padding,
+                min_width: style.min_width,
+                ..Default::default()
+            };
+            let menu = |r: &mut Self| {
+                r.col(|r| {
+                    for ((text, f), id) in items.iter().zip(&item_ids) {
+                        // the measuring pass must not call the function a second time
+                        if r.button(*id, text, item_style) && !r.measuring {
+                            f(r);
+                        }
+                    }
+                });
+            };
+            let (width, height) = self.measure_layout(|r| menu(r));
+            // the menu opens to the left or above the mouse if it doesn't fit otherwise
+            let x = if x + width > self.width() { (x - width).max(0.0) } else { x };
+            let y = if y + height > self.height() { (y - height).max(0.0) } else { y };
+            self.with_layer(OVERLAY_LAYER, |r| {
+                r.set_cursor_detached(x, y, |r| menu(r));
+            });
+
+            // clicking an item or anywhere else closes the menu
+            if self.mouse.lmouseclick {
+                position = None;
+            }
+        }
+        self.get_state::<ContextMenuState>(state_id).position = position;
+    }
+}
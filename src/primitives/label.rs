@@ -1,26 +1,45 @@
 use crate::color::Color;
-use crate::styling::Padding;
+use crate::font::{FontStyle, FontWeight};
+use crate::key::KeyCombo;
+use crate::styling::{Border, InheritedStyle, Margin, Padding};
+use crate::theme::Theme;
 use crate::renderer::Renderer;
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone)]
 pub struct LabelStyle {
     pub background_color: Option<Color>,
     pub foreground_color: Color,
     pub padding: Padding,
-    pub min_width: f32
+    pub margin: Margin,
+    pub min_width: f32,
+    pub border_radius: f32,
+    pub border: Option<Border>,
+    pub font_weight: FontWeight,
+    pub font_style: FontStyle,
+    /// shown right-aligned next to the text in a dimmer color, e.g. for menu items
+    pub shortcut_hint: Option<KeyCombo>
 }
 
 impl Default for LabelStyle {
     fn default() -> Self {
         Self {
-            background_color: None,
-            foreground_color: Color::BLACK,
+            background_color: InheritedStyle::current().background_color,
+            foreground_color: InheritedStyle::current().foreground_color.unwrap_or(Theme::current().foreground_color),
             padding: 0.0.into(),
-            min_width: 0.0
+            margin: 0.0.into(),
+            min_width: 0.0,
+            border_radius: 0.0,
+            border: None,
+            font_weight: FontWeight::Normal,
+            font_style: FontStyle::Normal,
+            shortcut_hint: None
         }
     }
 }
 
+/// the minimum space between the text and the shortcut hint
+const SHORTCUT_HINT_GAP: f32 = 24.0;
+
 impl Into<LabelStyle> for () {
     fn into(self) -> LabelStyle {
         Default::default()
@@ -35,15 +54,63 @@ impl Renderer {
         // Might change based on font and font size not sure yet.
         let font_sorcery = 2.0;
         let (x, y) = self.pos();
-        let (width, height) = self.calculate_text_size(text);
-        let rect_width = width.max(style.min_width) + style.padding.left + style.padding.right;
-        let rect_height = height + style.padding.top + style.padding.bottom - font_sorcery * 1.5;
+        let x = x + style.margin.left;
+        let y = y + style.margin.top;
+        let metrics = self.font().measure_text_styled(text, style.font_weight, style.font_style);
+        let (width, height) = (metrics.width, metrics.height);
+        let hint = style.shortcut_hint.map(|combo| combo.display_string());
+        let hint_width = hint.as_ref().map(|hint| SHORTCUT_HINT_GAP + self.calculate_text_size(hint).0).unwrap_or(0.0);
+        let rect_width = (width + hint_width).max(style.min_width) + style.padding.left + style.padding.right;
+        let natural_height = height + style.padding.top + style.padding.bottom - font_sorcery * 1.5;
+        let rect_height = match self.stretched_height() {
+            Some(stretched) => natural_height.max(stretched - style.margin.top - style.margin.bottom),
+            None => natural_height,
+        };
         let text_x = x + style.padding.left;
-        let text_y = y + style.padding.top - font_sorcery * 2.0;
-        self.rectangle((rect_width, rect_height), style.background_color.unwrap_or(self.background_color));
-        self.set_cursor(text_x, text_y, |r| {
-            r.text(text, style.foreground_color);
+        // the text stays centered when the label got stretched
+        let text_y = y + style.padding.top - font_sorcery * 2.0 + (rect_height - natural_height) / 2.0;
+        let background_color = style.background_color.unwrap_or(self.background_color);
+        self.set_cursor_detached(x, y, |r| {
+            match style.border {
+                // the border of a rounded label is an outer rounded rect with the background drawn on top
+                Some(border) if style.border_radius > 0.0 => {
+                    r.draw_rounded_rect((rect_width, rect_height), style.border_radius, border.color);
+                    r.set_cursor(x + border.width, y + border.width, |r| {
+                        r.draw_rounded_rect(
+                            (rect_width - 2.0 * border.width, rect_height - 2.0 * border.width),
+                            style.border_radius - border.width,
+                            background_color
+                        );
+                    });
+                }
+                Some(border) => {
+                    r.rectangle((rect_width, rect_height), background_color);
+                    r.set_cursor(x, y, |r| {
+                        r.draw_rectangle_outline((rect_width, rect_height), border.color, border.width);
+                    });
+                }
+                None if style.border_radius > 0.0 => {
+                    r.draw_rounded_rect((rect_width, rect_height), style.border_radius, background_color);
+                }
+                None => {
+                    r.rectangle((rect_width, rect_height), background_color);
+                }
+            }
+        });
+        self.set_cursor_detached(text_x, text_y, |r| {
+            r.text_styled(text, style.foreground_color, style.font_weight, style.font_style);
         });
+        if let Some(hint) = hint {
+            let hint_x = x + rect_width - style.padding.right - (hint_width - SHORTCUT_HINT_GAP);
+            self.set_cursor_detached(hint_x, text_y, |r| {
+                r.text(&hint, style.foreground_color.lerp(background_color, 0.5));
+            });
+        }
+        // the margin is part of the space the label takes up in the layout
+        self.handle_new_shape(
+            style.margin.left + rect_width + style.margin.right,
+            style.margin.top + rect_height + style.margin.bottom
+        );
 
         (rect_width, rect_height, text_x + width)
     }
@@ -0,0 +1,187 @@
+use crate::color::Color;
+use crate::styling::Padding;
+use crate::theme::Theme;
+use crate::renderer::{Renderer, OVERLAY_LAYER};
+use crate::primitives::ButtonStyle;
+
+/// an entry of a menu bar, items with children open a submenu instead of calling their action
+pub struct MenuItem<'a> {
+    pub label: &'a str,
+    pub children: &'a [MenuItem<'a>],
+    pub action: Option<Box<dyn Fn() + 'a>>
+}
+
+impl<'a> MenuItem<'a> {
+    pub fn new(label: &'a str, action: impl Fn() + 'a) -> Self {
+        Self {
+            label,
+            children: &[],
+            action: Some(Box::new(action))
+        }
+    }
+
+    pub fn submenu(label: &'a str, children: &'a [MenuItem<'a>]) -> Self {
+        Self {
+            label,
+            children,
+            action: None
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct MenuBarStyle {
+    pub background_color: Color,
+    pub hover_color: Color,
+    pub foreground_color: Color,
+    pub padding: Padding
+}
+
+impl Default for MenuBarStyle {
+    fn default() -> Self {
+        let theme = Theme::current();
+        Self {
+            background_color: theme.surface_color,
+            hover_color: theme.hover_color,
+            foreground_color: theme.foreground_color,
+            padding: Padding::symmetric(4.0, 8.0)
+        }
+    }
+}
+
+impl Into<MenuBarStyle> for () {
+    fn into(self) -> MenuBarStyle {
+        Default::default()
+    }
+}
+
+impl Renderer {
+    /// a row of menus spanning the remaining width, clicking one opens a dropdown of its children.
+    ///
+    /// Submenus open on hover, clicking an item without children calls its action and closes the menu.
+    pub fn menubar<T: Into<MenuBarStyle>>(&mut self, items: &[MenuItem], style: T) {
+        let style = style.into();
+        let item_style = ButtonStyle {
+            background_color: style.background_color,
+            hover_color: style.hover_color,
+            foreground_color: style.foreground_color,
+            padding: style.padding,
+            ..Default::default()
+        };
+        let ids: Vec<u32> = (0..items.len())
+            .map(|i| self.id_from_str(&format!("menubar {}", i)))
+            .collect();
+
+        let (x, y) = self.pos();
+        let mut open_path = self.open_menu_path.clone();
+        let mut menu_ids = ids.clone();
+        let mut dropdown_x = None;
+
+        let (_, bar_height) = self.measure_layout(|r| {
+            r.row(|r| {
+                for (item, id) in items.iter().zip(&ids) {
+                    r.button(*id, item.label, item_style);
+                }
+            });
+        });
+        let bar_width = self.remaining_width();
+        self.set_cursor_detached(x, y, |r| {
+            r.rectangle((bar_width, bar_height), style.background_color);
+        });
+        self.row(|r| {
+            for (i, (item, id)) in items.iter().zip(&ids).enumerate() {
+                let (item_x, _) = r.pos();
+                if r.button(*id, item.label, item_style) {
+                    open_path = if open_path.first() == Some(&i) { Vec::new() } else { vec![i] };
+                } else if r.is_hot(*id) && !open_path.is_empty() && open_path[0] != i {
+                    // while a menu is open hovering another one switches to it
+                    open_path = vec![i];
+                }
+                if open_path.first() == Some(&i) {
+                    dropdown_x = Some(item_x);
+                }
+            }
+        });
+
+        if let Some(dropdown_x) = dropdown_x {
+            let children = items[open_path[0]].children;
+            self.with_layer(OVERLAY_LAYER, |r| {
+                r.menu_dropdown(children, &mut open_path, 1, (dropdown_x, y + bar_height), item_style, &mut menu_ids);
+            });
+        }
+
+        // clicking anywhere outside of the menus closes them
+        let inside = self.hot_id.map(|hot| menu_ids.contains(&hot)).unwrap_or(false);
+        if self.mouse.lmouseclick && !inside {
+            open_path.clear();
+        }
+        // the measuring pass would toggle the menus a second time
+        if !self.measuring {
+            self.open_menu_path = open_path;
+        }
+    }
+
+    /// draws the items of an open menu at `pos` and the submenu at `path[depth]` next to it
+    fn menu_dropdown(
+        &mut self,
+        items: &[MenuItem],
+        path: &mut Vec<usize>,
+        depth: usize,
+        pos: (f32, f32),
+        style: ButtonStyle,
+        menu_ids: &mut Vec<u32>
+    ) {
+        if items.is_empty() {
+            return;
+        }
+        let prefix: Vec<String> = path[..depth].iter().map(|i| i.to_string()).collect();
+        let ids: Vec<u32> = (0..items.len())
+            .map(|i| self.id_from_str(&format!("menubar {} {}", prefix.join(" "), i)))
+            .collect();
+        let labels: Vec<String> = items
+            .iter()
+            .map(|item| if item.children.is_empty() { item.label.to_string() } else { format!("{}  >", item.label) })
+            .collect();
+
+        // every item is as wide as the widest one, so the menu forms a single block
+        let (width, _) = self.measure_layout(|r| {
+            for (label, id) in labels.iter().zip(&ids) {
+                r.button(*id, label, style);
+            }
+        });
+        let item_style = ButtonStyle {
+            min_width: width - style.padding.left - style.padding.right,
+            ..style
+        };
+
+        let mut submenu = None;
+        self.set_cursor_detached(pos.0, pos.1, |r| {
+            r.col(|r| {
+                for (i, (item, id)) in items.iter().zip(&ids).enumerate() {
+                    let (_, item_y) = r.pos();
+                    let clicked = r.button(*id, &labels[i], item_style);
+                    if r.is_hot(*id) {
+                        path.truncate(depth);
+                        if !item.children.is_empty() {
+                            path.push(i);
+                        }
+                    }
+                    if clicked && item.children.is_empty() && !r.measuring {
+                        if let Some(action) = &item.action {
+                            action();
+                        }
+                        path.clear();
+                    }
+                    if path.get(depth) == Some(&i) {
+                        submenu = Some((i, item_y));
+                    }
+                }
+            });
+        });
+        menu_ids.extend(ids);
+
+        if let Some((i, item_y)) = submenu {
+            self.menu_dropdown(items[i].children, path, depth + 1, (pos.0 + width, item_y), style, menu_ids);
+        }
+    }
+}
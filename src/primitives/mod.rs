@@ -1,5 +1,45 @@
+pub mod accordion;
+pub mod button;
+pub mod colorpicker;
+pub mod contextmenu;
 pub mod label;
+pub mod menubar;
+pub mod modal;
+pub mod ninepatch;
+pub mod numberinput;
+pub mod panel;
+pub mod richtext;
+pub mod slider;
+pub mod splitter;
+pub mod table;
+pub mod statusbar;
+pub mod tabs;
+pub mod textarea;
 pub mod textinput;
+pub mod toast;
+pub mod tooltip;
+pub mod treeview;
+pub mod virtuallist;
 
+pub use accordion::*;
+pub use button::*;
+pub use colorpicker::*;
+pub use contextmenu::*;
 pub use label::*;
+pub use menubar::*;
+pub use modal::*;
+pub use ninepatch::*;
+pub use numberinput::*;
+pub use panel::*;
+pub use richtext::*;
+pub use slider::*;
+pub use splitter::*;
+pub use table::*;
+pub use statusbar::*;
+pub use tabs::*;
+pub use textarea::*;
 pub use textinput::*;
+pub use toast::*;
+pub use tooltip::*;
+pub use treeview::*;
+pub use virtuallist::*;
@@ -0,0 +1,66 @@
+use crate::color::Color;
+use crate::styling::Padding;
+use crate::renderer::{Renderer, OVERLAY_LAYER};
+use crate::primitives::ButtonStyle;
+
+/// the space between the border of the modal and its content
+const MODAL_PADDING: f32 = 12.0;
+
+impl Renderer {
+    /// draws a dialog with the given title above everything else while `open` is true.
+    ///
+    /// Clicking the close button or anywhere outside of the dialog sets `open` to false.
+    pub fn modal(&mut self, open: &mut bool, title: &str, mut f: impl FnMut(&mut Self)) {
+        if !*open {
+            return;
+        }
+
+        let overlay_id = self.id_from_str(&format!("modal overlay {}", title));
+        let panel_id = self.id_from_str(&format!("modal panel {}", title));
+        let close_id = self.id_from_str(&format!("modal close {}", title));
+
+        let (title_width, title_height) = self.calculate_text_size(title);
+        let (close_width, _) = self.calculate_text_size("x");
+        let close_style = ButtonStyle {
+            padding: Padding::symmetric(6.0, 2.0),
+            ..Default::default()
+        };
+        let close_width = close_width + close_style.padding.left + close_style.padding.right;
+        let (content_width, content_height) = self.measure_layout(|r| f(r));
+
+        let width = content_width.max(title_width + MODAL_PADDING + close_width) + 2.0 * MODAL_PADDING;
+        let height = content_height + title_height + 3.0 * MODAL_PADDING;
+        let x = ((self.width() - width) / 2.0).max(0.0);
+        let y = ((self.height() - height) / 2.0).max(0.0);
+        let (viewport_width, viewport_height) = (self.width(), self.height());
+
+        let mut close = false;
+        self.with_layer(OVERLAY_LAYER, |r| {
+            r.set_cursor_detached(0.0, 0.0, |r| {
+                r.hitbox(overlay_id, |r, _, _, _| {
+                    r.rectangle((viewport_width, viewport_height), Color::rgba(0, 0, 0, 120));
+                });
+            });
+            r.set_cursor_detached(x, y, |r| {
+                r.hitbox(panel_id, |r, _, _, _| {
+                    r.rectangle((width, height), r.theme().surface_color);
+                });
+            });
+            r.set_cursor_detached(x + MODAL_PADDING, y + MODAL_PADDING, |r| {
+                r.text(title, r.theme().foreground_color);
+            });
+            r.set_cursor_detached(x + width - MODAL_PADDING - close_width, y + MODAL_PADDING, |r| {
+                if r.button(close_id, "x", close_style) {
+                    close = true;
+                }
+            });
+            r.set_cursor_detached(x + MODAL_PADDING, y + 2.0 * MODAL_PADDING + title_height, |r| {
+                r.col(|r| f(r));
+            });
+        });
+
+        if close || self.clicked(overlay_id) {
+            *open = false;
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::renderer::Renderer;
+
+/// the size of the borders of a nine patch image in pixels of the texture
+#[derive(Debug, Copy, Clone)]
+pub struct NinePatchSlices {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32
+}
+
+impl Into<NinePatchSlices> for f32 {
+    fn into(self) -> NinePatchSlices {
+        NinePatchSlices {
+            top: self,
+            right: self,
+            bottom: self,
+            left: self,
+        }
+    }
+}
+
+impl Renderer {
+    /// draws a texture stretched to `size` without stretching its corners.
+    ///
+    /// The edges only get stretched along their length and the center gets stretched in both directions.
+    pub fn nine_patch<T: Into<NinePatchSlices>>(&mut self, id: u32, size: (f32, f32), slices: T) {
+        let slices = slices.into();
+        let (tex_width, tex_height) = self.texture_size(id).expect("Texture not found");
+        let (tex_width, tex_height) = (tex_width as f32, tex_height as f32);
+        let (width, height) = size;
+        let (x, y) = self.pos();
+
+        let xs = [0.0, slices.left, width - slices.right, width];
+        let ys = [0.0, slices.top, height - slices.bottom, height];
+        let us = [0.0, slices.left / tex_width, 1.0 - slices.right / tex_width, 1.0];
+        let vs = [0.0, slices.top / tex_height, 1.0 - slices.bottom / tex_height, 1.0];
+
+        for row in 0..3 {
+            for col in 0..3 {
+                let cell_width = xs[col + 1] - xs[col];
+                let cell_height = ys[row + 1] - ys[row];
+                if cell_width <= 0.0 || cell_height <= 0.0 {
+                    continue;
+                }
+                self.set_cursor_detached(x + xs[col], y + ys[row], |r| {
+                    r.draw_texture_region(
+                        (cell_width, cell_height),
+                        id,
+                        (us[col], vs[row], us[col + 1], vs[row + 1])
+                    );
+                });
+            }
+        }
+
+        self.handle_new_shape(width, height);
+    }
+}
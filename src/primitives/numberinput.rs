@@ -0,0 +1,114 @@
+use crate::color::Color;
+use crate::styling::Padding;
+use crate::theme::Theme;
+use crate::renderer::Renderer;
+use crate::primitives::{ButtonStyle, TextInputState, TextInputStyle};
+use crate::key::Key;
+
+#[derive(Debug, Copy, Clone)]
+pub struct NumberInputStyle {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub decimal_places: usize,
+    pub background_color: Option<Color>,
+    pub foreground_color: Color,
+    pub padding: Padding,
+    pub min_width: f32
+}
+
+impl NumberInputStyle {
+    fn clamp(&self, value: f64) -> f64 {
+        let value = self.min.map(|min| value.max(min)).unwrap_or(value);
+        self.max.map(|max| value.min(max)).unwrap_or(value)
+    }
+
+    fn format(&self, value: f64) -> String {
+        format!("{:.*}", self.decimal_places, value)
+    }
+}
+
+impl Default for NumberInputStyle {
+    fn default() -> Self {
+        Self {
+            min: None,
+            max: None,
+            decimal_places: 0,
+            background_color: Some(Theme::current().surface_color),
+            foreground_color: Theme::current().foreground_color,
+            padding: Padding::symmetric(4.0, 2.0),
+            min_width: 60.0
+        }
+    }
+}
+
+impl Into<NumberInputStyle> for () {
+    fn into(self) -> NumberInputStyle {
+        Default::default()
+    }
+}
+
+#[derive(Default)]
+struct NumberInputState {
+    text: TextInputState,
+    /// whether the text field was active last frame
+    editing: bool
+}
+
+impl Renderer {
+    /// returns true if the value changed this frame.
+    ///
+    /// Typed values only get applied once the text field loses focus or enter is pressed,
+    /// invalid values get replaced with the previous value.
+    pub fn number_input<T: Into<NumberInputStyle>>(&mut self, id: u32, value: &mut f64, step: f64, style: T) -> bool {
+        let style = style.into();
+        let decrement_id = self.id_from_str(&format!("{} decrement", id));
+        let increment_id = self.id_from_str(&format!("{} increment", id));
+        let button_style = ButtonStyle {
+            padding: style.padding,
+            ..Default::default()
+        };
+        let text_style = TextInputStyle {
+            background_color: style.background_color,
+            foreground_color: style.foreground_color,
+            padding: style.padding,
+            min_width: style.min_width,
+            ..Default::default()
+        };
+
+        let mut state = std::mem::take(self.get_state::<NumberInputState>(id));
+        let previous = *value;
+
+        if self.is_active(id) && (self.is_key_pressed(Key::Enter) || self.is_key_pressed(Key::NumpadEnter)) {
+            self.active_id = None;
+        }
+        if state.editing && !self.is_active(id) {
+            match state.text.value.trim().parse::<f64>() {
+                Ok(typed) if typed.is_finite() => *value = style.clamp(typed),
+                _ => {}
+            }
+        }
+        state.editing = self.is_active(id);
+        // otherwise the text field would be empty until the end of the first frame
+        if !state.editing {
+            state.text = TextInputState::new(&style.format(*value));
+        }
+
+        self.row(|r| {
+            if r.button(decrement_id, "\u{2212}", button_style) {
+                *value = style.clamp(*value - step);
+            }
+            r.text_input(id, &mut state.text, text_style);
+            if r.button(increment_id, "+", button_style) {
+                *value = style.clamp(*value + step);
+            }
+        });
+
+        // the text only follows the value while it is not being edited, e.g. after the buttons changed it
+        if !state.editing {
+            state.text = TextInputState::new(&style.format(*value));
+        }
+        *self.get_state::<NumberInputState>(id) = state;
+
+        *value != previous
+    }
+}
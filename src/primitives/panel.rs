@@ -0,0 +1,63 @@
+use crate::renderer::Renderer;
+
+/// the edge of the window a panel is attached to
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DockSide {
+    Left,
+    Right,
+    Top,
+    Bottom
+}
+
+impl Renderer {
+    /// a panel of `size` pixels along the given edge of the content area, `f` is laid out in a column inside of it.
+    ///
+    /// The space of the panel is taken away from the content area, so panels docked afterwards are placed next to it
+    /// and the cursor gets moved out from under the panel. Panels docked inside `f` split up this panel instead.
+    pub fn panel(&mut self, id: u32, dock: DockSide, size: f32, mut f: impl FnMut(&mut Self)) {
+        let (x, y, width, height) = self.content_area;
+        let size = size.max(0.0).min(match dock {
+            DockSide::Left | DockSide::Right => width,
+            DockSide::Top | DockSide::Bottom => height,
+        });
+        let (panel, content) = match dock {
+            DockSide::Left => ((x, y, size, height), (x + size, y, width - size, height)),
+            DockSide::Right => ((x + width - size, y, size, height), (x, y, width - size, height)),
+            DockSide::Top => ((x, y, width, size), (x, y + size, width, height - size)),
+            DockSide::Bottom => ((x, y + height - size, width, size), (x, y, width, height - size)),
+        };
+
+        let (panel_x, panel_y, panel_width, panel_height) = panel;
+        let background_color = self.theme().surface_color;
+        self.set_cursor_detached(panel_x, panel_y, |r| {
+            // the hitbox keeps clicks on the panel from reaching the widgets below it
+            r.hitbox(id, |r, _, _, _| {
+                r.rectangle((panel_width, panel_height), background_color);
+            });
+        });
+        if let Some(hitbox) = self.hitboxes.get_mut(&id) {
+            hitbox.focusable = false;
+        }
+
+        let previous = self.content_area;
+        self.content_area = panel;
+        self.begin_clip(panel_x, panel_y, panel_width, panel_height);
+        self.set_cursor_detached(panel_x, panel_y, |r| r.col(|r| f(r)));
+        self.end_clip();
+        if self.measuring {
+            self.content_area = previous;
+            return;
+        }
+        self.content_area = content;
+        let (content_x, content_y, _, _) = content;
+        let (cursor_x, cursor_y) = self.pos();
+        self.cursor = (cursor_x.max(content_x), cursor_y.max(content_y));
+    }
+
+    /// the area of the window that isn't covered by panels as `(x, y, width, height)`, inside of `panel` it is the panel itself.
+    ///
+    /// `remaining_width` and `remaining_height` end at its edges.
+    pub fn content_area(&self) -> (f32, f32, f32, f32) {
+        self.content_area
+    }
+}
@@ -0,0 +1,66 @@
+use crate::color::Color;
+use crate::renderer::Renderer;
+
+/// the thickness of underlines and strikethroughs
+const LINE_THICKNESS: f32 = 1.0;
+
+/// a piece of text with its own color and decorations, see `Renderer::rich_text`
+#[derive(Debug, Copy, Clone)]
+pub struct TextSpan<'a> {
+    pub text: &'a str,
+    pub color: Color,
+    pub underline: bool,
+    pub strikethrough: bool
+}
+
+impl<'a> TextSpan<'a> {
+    pub fn new(text: &'a str, color: Color) -> Self {
+        Self {
+            text,
+            color,
+            underline: false,
+            strikethrough: false
+        }
+    }
+
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    pub fn strikethrough(mut self) -> Self {
+        self.strikethrough = true;
+        self
+    }
+}
+
+impl Renderer {
+    /// draws the spans next to each other on a single line and returns the size of the whole line
+    pub fn rich_text(&mut self, spans: &[TextSpan]) -> (f32, f32) {
+        let (x, y) = self.pos();
+        let height = self.font().size as f32;
+        let mut width = 0.0;
+        for span in spans {
+            let span_width = self.font().measure_text(span.text).width;
+            let span_x = x + width;
+            self.set_cursor_detached(span_x, y, |r| {
+                r.text(span.text, span.color);
+            });
+            if span.underline {
+                self.set_cursor_detached(span_x, y + height - LINE_THICKNESS, |r| {
+                    r.rectangle((span_width, LINE_THICKNESS), span.color);
+                });
+            }
+            if span.strikethrough {
+                // roughly the middle of the lower case letters
+                self.set_cursor_detached(span_x, y + height * 0.6, |r| {
+                    r.rectangle((span_width, LINE_THICKNESS), span.color);
+                });
+            }
+            width += span_width;
+        }
+        self.handle_new_shape(width, height);
+
+        (width, height)
+    }
+}
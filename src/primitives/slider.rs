@@ -0,0 +1,80 @@
+use crate::color::Color;
+use crate::theme::Theme;
+use crate::renderer::{CursorIcon, Renderer};
+use crate::styling::FocusStyle;
+
+#[derive(Debug, Copy, Clone)]
+pub struct SliderStyle {
+    pub width: f32,
+    pub height: f32,
+    pub track_color: Color,
+    pub fill_color: Color,
+    pub thumb_color: Color,
+    pub thumb_width: f32,
+    pub focus: FocusStyle
+}
+
+impl Default for SliderStyle {
+    fn default() -> Self {
+        let theme = Theme::current();
+        Self {
+            width: 150.0,
+            height: 16.0,
+            track_color: theme.hover_color,
+            fill_color: theme.accent_color,
+            thumb_color: theme.thumb_color,
+            thumb_width: 6.0,
+            focus: Default::default()
+        }
+    }
+}
+
+impl Into<SliderStyle> for () {
+    fn into(self) -> SliderStyle {
+        Default::default()
+    }
+}
+
+/// the height of the track relative to the height of the slider
+const TRACK_HEIGHT: f32 = 0.25;
+
+impl Renderer {
+    /// a horizontal slider which can be dragged to pick a value between `min` and `max`.
+    ///
+    /// Returns true if the value changed this frame.
+    pub fn slider<T: Into<SliderStyle>>(&mut self, id: u32, value: &mut f32, min: f32, max: f32, style: T) -> bool {
+        let style = style.into();
+        let previous = *value;
+        let (x, y) = self.pos();
+
+        if !self.measuring && (self.is_hot(id) || self.is_active(id)) && self.mouse.lmousedown {
+            let progress = ((self.mouse.x as f32 - x) / style.width).max(0.0).min(1.0);
+            *value = min + (max - min) * progress;
+        }
+        let progress = if max > min { ((*value - min) / (max - min)).max(0.0).min(1.0) } else { 0.0 };
+
+        self.hitbox(id, |r, hot, _active, focused| {
+            if hot {
+                r.set_cursor_icon(CursorIcon::Hand);
+            }
+            let track_height = style.height * TRACK_HEIGHT;
+            let track_y = y + (style.height - track_height) / 2.0;
+            r.set_cursor_detached(x, track_y, |r| {
+                r.rectangle((style.width, track_height), style.track_color);
+            });
+            r.set_cursor_detached(x, track_y, |r| {
+                r.rectangle((style.width * progress, track_height), style.fill_color);
+            });
+            let thumb_x = x + (style.width - style.thumb_width) * progress;
+            r.set_cursor_detached(thumb_x, y, |r| {
+                r.rectangle((style.thumb_width, style.height), style.thumb_color);
+            });
+            r.handle_new_shape(style.width, style.height);
+            if focused {
+                r.draw_focus_ring(style.focus.color, style.focus.thickness);
+            }
+        });
+
+        *value != previous
+    }
+}
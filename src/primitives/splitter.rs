@@ -0,0 +1,84 @@
+use crate::theme::Theme;
+use crate::renderer::{CursorIcon, Renderer};
+
+/// the width of the handle between the panes
+const HANDLE_SIZE: f32 = 4.0;
+
+impl Renderer {
+    /// splits the remaining space into a left and a right pane, `ratio` is the fraction of the width the left pane takes up.
+    ///
+    /// The handle between the panes can be dragged to change `ratio`.
+    pub fn vsplit(&mut self, id: u32, ratio: &mut f32, left: impl FnMut(&mut Self), right: impl FnMut(&mut Self)) {
+        self.split(id, ratio, true, left, right);
+    }
+
+    /// like `vsplit`, but with a top and a bottom pane, `ratio` is the fraction of the height the top pane takes up
+    pub fn hsplit(&mut self, id: u32, ratio: &mut f32, top: impl FnMut(&mut Self), bottom: impl FnMut(&mut Self)) {
+        self.split(id, ratio, false, top, bottom);
+    }
+
+    fn split(
+        &mut self,
+        id: u32,
+        ratio: &mut f32,
+        vertical: bool,
+        mut first: impl FnMut(&mut Self),
+        mut second: impl FnMut(&mut Self)
+    ) {
+        let (x, y) = self.pos();
+        let (width, height) = (self.remaining_width(), self.remaining_height());
+        let available = if vertical { width } else { height } - HANDLE_SIZE;
+
+        if !self.measuring && (self.is_hot(id) || self.is_active(id)) && self.mouse.lmousedown && available > 0.0 {
+            // the handle stays centered under the mouse
+            let offset = if vertical {
+                self.mouse.x as f32 - x
+            } else {
+                self.mouse.y as f32 - y
+            };
+            *ratio = (offset - HANDLE_SIZE / 2.0) / available;
+        }
+        *ratio = ratio.max(0.0).min(1.0);
+
+        let first_size = available.max(0.0) * *ratio;
+        let second_start = first_size + HANDLE_SIZE;
+        let (first_rect, handle_rect, second_rect) = if vertical {
+            (
+                (x, y, first_size, height),
+                (x + first_size, y, HANDLE_SIZE, height),
+                (x + second_start, y, width - second_start, height)
+            )
+        } else {
+            (
+                (x, y, width, first_size),
+                (x, y + first_size, width, HANDLE_SIZE),
+                (x, y + second_start, width, height - second_start)
+            )
+        };
+
+        self.split_pane(first_rect, |r| first(r));
+        self.split_pane(second_rect, |r| second(r));
+
+        let (handle_x, handle_y, handle_width, handle_height) = handle_rect;
+        self.set_cursor_detached(handle_x, handle_y, |r| {
+            r.hitbox(id, |r, hot, active, _| {
+                if hot || active {
+                    r.set_cursor_icon(if vertical { CursorIcon::ColResize } else { CursorIcon::RowResize });
+                }
+                let theme = Theme::current();
+                let color = if hot || active { theme.accent_color } else { theme.hover_color };
+                r.rectangle((handle_width, handle_height), color);
+            });
+        });
+
+        self.handle_new_shape(width, height);
+    }
+
+    /// lays out `f` in a column that gets clipped to `rect`
+    fn split_pane(&mut self, rect: (f32, f32, f32, f32), mut f: impl FnMut(&mut Self)) {
+        let (x, y, width, height) = rect;
+        self.begin_clip(x, y, width.max(0.0), height.max(0.0));
+        self.set_cursor_detached(x, y, |r| r.col(|r| f(r)));
+        self.end_clip();
+    }
+}
@@ -0,0 +1,39 @@
+use crate::renderer::Renderer;
+
+/// the space between the text of a section and its left edge
+const SECTION_PADDING: f32 = 6.0;
+/// the width of the line between two sections
+const DIVIDER_WIDTH: f32 = 1.0;
+
+impl Renderer {
+    /// a bar of the given height at the bottom of the window, `f` is laid out in a row inside of it.
+    ///
+    /// The bar doesn't take up any space in the current layout, use `statusbar_section` to fill it.
+    pub fn statusbar(&mut self, height: f32, mut f: impl FnMut(&mut Self)) {
+        let width = self.width();
+        let background_color = self.theme().surface_color;
+        self.set_cursor_detached(0.0, -height, |r| {
+            r.rectangle((width, height), background_color);
+        });
+        self.set_cursor_detached(0.0, -height, |r| r.row(|r| f(r)));
+    }
+
+    /// a region of the status bar with the given text, followed by a divider.
+    ///
+    /// The section is at least `width` wide and grows to fit the text.
+    pub fn statusbar_section(&mut self, text: &str, width: f32) {
+        let (x, y) = self.pos();
+        // the status bar is at the bottom, so its height is whatever is left of the window, panels don't cover it
+        let height = self.height() - y;
+        let (text_width, text_height) = self.calculate_text_size(text);
+        let width = width.max(text_width + 2.0 * SECTION_PADDING + DIVIDER_WIDTH);
+        let (foreground_color, divider_color) = (self.theme().foreground_color, self.theme().hover_color);
+        self.set_cursor_detached(x + SECTION_PADDING, y + ((height - text_height) / 2.0).max(0.0), |r| {
+            r.text(text, foreground_color);
+        });
+        self.set_cursor_detached(x + width - DIVIDER_WIDTH, y, |r| {
+            r.rectangle((DIVIDER_WIDTH, height), divider_color);
+        });
+        self.handle_new_shape(width, height);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::color::Color;
+use crate::theme::Theme;
+use crate::renderer::Renderer;
+
+#[derive(Debug, Copy, Clone)]
+pub struct TableStyle {
+    pub row_height: f32,
+    pub header_background: Color,
+    pub header_foreground: Color,
+    pub row_background: Color,
+    /// the background of every second row
+    pub alternate_row_background: Color,
+    /// the space between the border of a cell and its content
+    pub cell_padding: f32
+}
+
+impl Default for TableStyle {
+    fn default() -> Self {
+        let theme = Theme::current();
+        Self {
+            row_height: 24.0,
+            header_background: theme.hover_color,
+            header_foreground: theme.foreground_color,
+            row_background: theme.surface_color,
+            alternate_row_background: theme.alternate_surface_color,
+            cell_padding: 4.0
+        }
+    }
+}
+
+impl Into<TableStyle> for () {
+    fn into(self) -> TableStyle {
+        Default::default()
+    }
+}
+
+impl Renderer {
+    /// draws a table with a header row and `row_count` rows.
+    ///
+    /// `columns` are the headers and widths of the columns. `render_cell` gets called with the row and column
+    /// of each cell and the cursor at the top left of the cell, anything outside of the cell gets clipped.
+    pub fn table<T: Into<TableStyle>>(
+        &mut self,
+        id: u32,
+        columns: &[(&str, f32)],
+        row_count: usize,
+        mut render_cell: impl FnMut(&mut Self, usize, usize),
+        style: T,
+    ) {
+        let style = style.into();
+        let (x, y) = self.pos();
+        let width: f32 = columns.iter().map(|(_, width)| width).sum();
+        let height = (row_count + 1) as f32 * style.row_height;
+        let padding = style.cell_padding;
+
+        self.hitbox(id, |r, _, _, _| {
+            r.set_cursor_detached(x, y, |r| {
+                r.rectangle((width, style.row_height), style.header_background);
+            });
+            let mut column_x = x;
+            for (header, column_width) in columns {
+                r.begin_clip(column_x, y, *column_width, style.row_height);
+                r.set_cursor_detached(column_x + padding, y + padding, |r| {
+                    r.text(header, style.header_foreground);
+                });
+                r.end_clip();
+                column_x += column_width;
+            }
+
+            for row in 0..row_count {
+                let row_y = y + (row + 1) as f32 * style.row_height;
+                let background = if row % 2 == 1 {
+                    style.alternate_row_background
+                } else {
+                    style.row_background
+                };
+                r.set_cursor_detached(x, row_y, |r| {
+                    r.rectangle((width, style.row_height), background);
+                });
+
+                let mut column_x = x;
+                for (column, (_, column_width)) in columns.iter().enumerate() {
+                    r.begin_clip(column_x, row_y, *column_width, style.row_height);
+                    r.set_cursor_detached(column_x + padding, row_y + padding, |r| {
+                        r.col(|r| render_cell(r, row, column));
+                    });
+                    r.end_clip();
+                    column_x += column_width;
+                }
+            }
+
+            r.handle_new_shape(width, height);
+        });
+    }
+}
@@ -0,0 +1,40 @@
+use crate::styling::Padding;
+use crate::renderer::Renderer;
+use crate::primitives::ButtonStyle;
+
+/// the height of the line below the selected tab
+const TAB_INDICATOR_HEIGHT: f32 = 2.0;
+
+impl Renderer {
+    /// draws a row of tabs and calls `f` with the index of the selected tab below them.
+    ///
+    /// Clicking a tab changes `selected` before `f` gets called.
+    pub fn tabs(&mut self, id: u32, tabs: &[&str], selected: &mut usize, mut f: impl FnMut(&mut Self, usize)) {
+        let style = ButtonStyle {
+            background_color: self.background_color,
+            padding: Padding::symmetric(8.0, 4.0),
+            ..Default::default()
+        };
+
+        self.col(|r| {
+            r.row(|r| {
+                for (i, tab) in tabs.iter().enumerate() {
+                    let tab_id = r.id_from_str(&format!("{} tab {}", id, i));
+                    let (x, y) = r.pos();
+                    let (width, height) = r.measure_layout(|r| {
+                        r.button(tab_id, tab, style);
+                    });
+                    if r.button(tab_id, tab, style) {
+                        *selected = i;
+                    }
+                    if i == *selected {
+                        r.set_cursor_detached(x, y + height - TAB_INDICATOR_HEIGHT, |r| {
+                            r.rectangle((width, TAB_INDICATOR_HEIGHT), r.theme().accent_color);
+                        });
+                    }
+                }
+            });
+            f(r, *selected);
+        });
+    }
+}
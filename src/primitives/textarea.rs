@@ -0,0 +1,339 @@
+use crate::color::Color;
+use crate::styling::{Border, FocusStyle, InheritedStyle, Margin, Padding};
+use crate::theme::Theme;
+use crate::renderer::{CursorIcon, Renderer};
+use crate::key::Key;
+
+use chrono::prelude::*;
+
+use std::time::Duration;
+
+/// drawn on top of the selected characters, so it has to be translucent
+const SELECTION_COLOR: Color = Color::rgba(0, 120, 215, 100);
+
+/// a position in a text area as `(row, column)`, where the column counts characters
+pub type TextPosition = (usize, usize);
+
+#[derive(Debug)]
+pub struct TextAreaState {
+    pub lines: Vec<String>,
+    pub cursor: TextPosition,
+    /// the selected characters as `(anchor, cursor)`, the anchor stays in place while the selection is extended
+    pub selection: Option<(TextPosition, TextPosition)>,
+    /// the first visible line
+    pub scroll: usize,
+    pub last_typed_at: DateTime<Local>
+}
+
+impl TextAreaState {
+    pub fn new(s: &str) -> Self {
+        let lines: Vec<String> = s.split('\n').map(|line| line.to_string()).collect();
+        let cursor = (lines.len() - 1, lines[lines.len() - 1].chars().count());
+        Self {
+            lines,
+            cursor,
+            selection: None,
+            scroll: 0,
+            last_typed_at: Local::now()
+        }
+    }
+
+    pub fn value(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    /// the selected characters as an ordered range, `None` if nothing is selected
+    pub fn selected_range(&self) -> Option<(TextPosition, TextPosition)> {
+        self.selection
+            .map(|(anchor, head)| (anchor.min(head), anchor.max(head)))
+            .filter(|(start, end)| start != end)
+    }
+
+    /// moves the text cursor, extending the selection if `extend` is true and clearing it otherwise
+    pub fn move_cursor(&mut self, pos: TextPosition, extend: bool) {
+        self.selection = if extend {
+            let anchor = self.selection.map(|(anchor, _)| anchor).unwrap_or(self.cursor);
+            Some((anchor, pos))
+        } else {
+            None
+        };
+        self.cursor = pos;
+    }
+
+    /// removes the selected characters and returns whether there was anything to remove
+    fn delete_selection(&mut self) -> bool {
+        let range = self.selected_range();
+        self.selection = None;
+        match range {
+            Some((start, end)) => {
+                let tail = self.lines[end.0][byte_index(&self.lines[end.0], end.1)..].to_string();
+                let start_index = byte_index(&self.lines[start.0], start.1);
+                self.lines[start.0].truncate(start_index);
+                self.lines[start.0].push_str(&tail);
+                self.lines.drain(start.0 + 1..=end.0);
+                self.cursor = start;
+                true
+            }
+            None => false
+        }
+    }
+
+    fn insert(&mut self, c: char) {
+        self.delete_selection();
+        let (row, col) = self.cursor;
+        let index = byte_index(&self.lines[row], col);
+        self.lines[row].insert(index, c);
+        self.cursor = (row, col + 1);
+    }
+
+    fn split_line(&mut self) {
+        self.delete_selection();
+        let (row, col) = self.cursor;
+        let index = byte_index(&self.lines[row], col);
+        let rest = self.lines[row].split_off(index);
+        self.lines.insert(row + 1, rest);
+        self.cursor = (row + 1, 0);
+    }
+
+    fn backspace(&mut self) {
+        if self.delete_selection() {
+            return;
+        }
+        match self.cursor {
+            (0, 0) => {}
+            // the line gets merged into the previous one
+            (row, 0) => {
+                let line = self.lines.remove(row);
+                let col = self.line_len(row - 1);
+                self.lines[row - 1].push_str(&line);
+                self.cursor = (row - 1, col);
+            }
+            (row, col) => {
+                let index = byte_index(&self.lines[row], col - 1);
+                self.lines[row].remove(index);
+                self.cursor = (row, col - 1);
+            }
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.delete_selection() {
+            return;
+        }
+        let (row, col) = self.cursor;
+        if col < self.line_len(row) {
+            let index = byte_index(&self.lines[row], col);
+            self.lines[row].remove(index);
+        } else if row + 1 < self.lines.len() {
+            let line = self.lines.remove(row + 1);
+            self.lines[row].push_str(&line);
+        }
+    }
+
+    fn left(&self) -> TextPosition {
+        match self.cursor {
+            (0, 0) => (0, 0),
+            (row, 0) => (row - 1, self.line_len(row - 1)),
+            (row, col) => (row, col - 1),
+        }
+    }
+
+    fn right(&self) -> TextPosition {
+        let (row, col) = self.cursor;
+        if col < self.line_len(row) {
+            (row, col + 1)
+        } else if row + 1 < self.lines.len() {
+            (row + 1, 0)
+        } else {
+            (row, col)
+        }
+    }
+
+    fn up(&self) -> TextPosition {
+        match self.cursor {
+            (0, _) => (0, 0),
+            (row, col) => (row - 1, col.min(self.line_len(row - 1))),
+        }
+    }
+
+    fn down(&self) -> TextPosition {
+        let (row, col) = self.cursor;
+        if row + 1 < self.lines.len() {
+            (row + 1, col.min(self.line_len(row + 1)))
+        } else {
+            (row, self.line_len(row))
+        }
+    }
+}
+
+/// the byte offset of the character at `col`, or the length of the line if it is past the end
+fn byte_index(line: &str, col: usize) -> usize {
+    line.char_indices()
+        .nth(col)
+        .map(|(i, _)| i)
+        .unwrap_or(line.len())
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct TextAreaStyle {
+    pub background_color: Option<Color>,
+    pub foreground_color: Color,
+    pub padding: Padding,
+    pub margin: Margin,
+    pub width: f32,
+    /// lines that don't fit into the height get clipped, the text area scrolls to keep the cursor visible
+    pub height: f32,
+    pub border: Option<Border>,
+    pub focus: FocusStyle
+}
+
+impl Default for TextAreaStyle {
+    fn default() -> Self {
+        Self {
+            background_color: InheritedStyle::current().background_color,
+            foreground_color: InheritedStyle::current().foreground_color.unwrap_or(Theme::current().foreground_color),
+            padding: 0.0.into(),
+            margin: 0.0.into(),
+            width: 200.0,
+            height: 100.0,
+            border: None,
+            focus: Default::default()
+        }
+    }
+}
+
+impl Into<TextAreaStyle> for () {
+    fn into(self) -> TextAreaStyle {
+        Default::default()
+    }
+}
+
+impl Renderer {
+    pub fn text_area<T: Into<TextAreaStyle>>(&mut self, id: u32, state: &mut TextAreaState, style: T) {
+        let style = style.into();
+        self.hitbox(id, move |r, hot, active, focused| {
+            if hot {
+                r.set_cursor_icon(CursorIcon::Text);
+            }
+            // the lines might have been changed from the outside
+            if state.lines.is_empty() {
+                state.lines.push(String::new());
+            }
+            let row = state.cursor.0.min(state.lines.len() - 1);
+            state.cursor = (row, state.cursor.1.min(state.line_len(row)));
+
+            if active {
+                r.handle_text_area_input(state);
+            }
+
+            let (x, y) = r.pos();
+            let x = x + style.margin.left;
+            let y = y + style.margin.top;
+            let rect_width = style.width + style.padding.left + style.padding.right;
+            let rect_height = style.height + style.padding.top + style.padding.bottom;
+            let background_color = style.background_color.unwrap_or(r.background_color);
+            r.set_cursor_detached(x, y, |r| {
+                r.rectangle((rect_width, rect_height), background_color);
+                if let Some(border) = style.border {
+                    r.set_cursor(x, y, |r| {
+                        r.draw_rectangle_outline((rect_width, rect_height), border.color, border.width);
+                    });
+                }
+            });
+
+            let line_height = r.font().size as f32;
+            let visible_lines = ((style.height / line_height) as usize).max(1);
+            let (cursor_row, cursor_col) = state.cursor;
+            if cursor_row < state.scroll {
+                state.scroll = cursor_row;
+            } else if cursor_row >= state.scroll + visible_lines {
+                state.scroll = cursor_row + 1 - visible_lines;
+            }
+
+            let text_x = x + style.padding.left;
+            let text_y = y + style.padding.top;
+            let selection = state.selected_range();
+            r.begin_clip(text_x, text_y, style.width, style.height);
+            for (i, line) in state.lines.iter().enumerate().skip(state.scroll).take(visible_lines + 1) {
+                let line_y = text_y + (i - state.scroll) as f32 * line_height;
+                if let Some((start, end)) = selection.filter(|(start, end)| start.0 <= i && i <= end.0) {
+                    let advance_widths = r.font().measure_text(line).advance_widths;
+                    let offset = |col: usize| text_x + advance_widths[..col.min(advance_widths.len())].iter().sum::<f32>();
+                    let from = if start.0 == i { offset(start.1) } else { text_x };
+                    // selected line breaks are shown as a small extra block
+                    let to = if end.0 == i { offset(end.1) } else { offset(advance_widths.len()) + line_height / 3.0 };
+                    r.set_cursor_detached(from, line_y, |r| {
+                        r.rectangle((to - from, line_height), SELECTION_COLOR);
+                    });
+                }
+                r.set_cursor_detached(text_x, line_y, |r| {
+                    r.text(line, style.foreground_color);
+                });
+            }
+
+            let current_millis = (Local::now() - state.last_typed_at).num_milliseconds() % 1000;
+            if active {
+                // the cursor blinks every 500ms
+                r.schedule_repaint_in(Duration::from_millis(500 - (current_millis % 500) as u64));
+            }
+            if active && current_millis < 500 {
+                let line = &state.lines[cursor_row];
+                let (prefix_width, _) = r.calculate_text_size(&line[..byte_index(line, cursor_col)]);
+                let cursor_y = text_y + (cursor_row - state.scroll) as f32 * line_height;
+                r.set_cursor_detached(text_x + prefix_width, cursor_y, |r| {
+                    r.rectangle((1.5, line_height), style.foreground_color);
+                });
+            }
+            r.end_clip();
+
+            // the margin is part of the space the text area takes up in the layout
+            r.handle_new_shape(
+                style.margin.left + rect_width + style.margin.right,
+                style.margin.top + rect_height + style.margin.bottom
+            );
+            if focused {
+                r.draw_focus_ring(style.focus.color, style.focus.thickness);
+            }
+        });
+    }
+
+    fn handle_text_area_input(&mut self, state: &mut TextAreaState) {
+        let mut changed = false;
+        for c in self.consume_input() {
+            // line breaks are handled through the enter key, characters without a glyph couldn't be drawn
+            if !c.is_control() && self.has_glyph(c) {
+                state.insert(c);
+            }
+            changed = true;
+        }
+        let shift = self.is_shift();
+        let ctrl = self.is_ctrl();
+        for k in self.consume_keys() {
+            match k {
+                Key::A if ctrl => {
+                    let last = state.lines.len() - 1;
+                    state.selection = Some(((0, 0), (last, state.line_len(last))));
+                    state.cursor = (last, state.line_len(last));
+                }
+                Key::Enter | Key::NumpadEnter => state.split_line(),
+                Key::Backspace => state.backspace(),
+                Key::Delete => state.delete(),
+                Key::Left => state.move_cursor(state.left(), shift),
+                Key::Right => state.move_cursor(state.right(), shift),
+                Key::Up => state.move_cursor(state.up(), shift),
+                Key::Down => state.move_cursor(state.down(), shift),
+                Key::Home => state.move_cursor((state.cursor.0, 0), shift),
+                Key::End => state.move_cursor((state.cursor.0, state.line_len(state.cursor.0)), shift),
+                _ => continue
+            }
+            changed = true;
+        }
+        if changed {
+            state.last_typed_at = Local::now();
+        }
+    }
+}
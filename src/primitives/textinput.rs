@@ -1,14 +1,33 @@
 use crate::color::Color;
-use crate::styling::Padding;
-use crate::renderer::Renderer;
+use crate::styling::{Border, FocusStyle, InheritedStyle, Margin, Padding};
+use crate::theme::Theme;
+use crate::renderer::{CursorIcon, Renderer};
 use crate::primitives::LabelStyle;
 use crate::key::Key;
 
+use arboard::Clipboard;
 use chrono::prelude::*;
 
+use std::time::Duration;
+
+/// drawn on top of the selected characters, so it has to be translucent
+const SELECTION_COLOR: Color = Color::rgba(0, 120, 215, 100);
+
 #[derive(Debug)]
 pub struct TextInputState {
     pub value: String,
+    /// shown in place of the value while it is empty and the input is not active
+    pub placeholder: Option<String>,
+    /// the number of characters in front of the text cursor
+    pub cursor_pos: usize,
+    /// the selected characters as `(anchor, cursor_pos)`, the anchor stays in place while the selection is extended
+    pub selection: Option<(usize, usize)>,
+    /// snapshots of the value and cursor position used for undo/redo
+    pub history: Vec<(String, usize)>,
+    /// the snapshot that matches the current value
+    pub history_index: usize,
+    /// the oldest snapshots get dropped once there are more than this
+    pub max_history: usize,
     pub last_typed_at: DateTime<Local>
 }
 
@@ -16,9 +35,127 @@ impl TextInputState {
     pub fn new(s: &str) -> Self {
         Self {
             value: s.to_string(),
+            placeholder: None,
+            cursor_pos: s.chars().count(),
+            selection: None,
+            history: vec![(s.to_string(), s.chars().count())],
+            history_index: 0,
+            max_history: 100,
             last_typed_at: Local::now()
         }
     }
+
+    /// uses the text in the clipboard as the initial value, the value is empty if the clipboard doesn't contain text
+    pub fn from_clipboard(clipboard: &mut Clipboard) -> Self {
+        Self::new(&clipboard.get_text().unwrap_or_default())
+    }
+
+    /// pushes a snapshot if the value changed since the last one, dropping the redo history
+    fn record_history(&mut self) {
+        if self.history.get(self.history_index).map(|(value, _)| value) == Some(&self.value) {
+            return;
+        }
+        self.history.truncate(self.history_index + 1);
+        self.history.push((self.value.clone(), self.cursor_pos));
+        if self.history.len() > self.max_history {
+            self.history.drain(..self.history.len() - self.max_history);
+        }
+        self.history_index = self.history.len() - 1;
+    }
+
+    fn restore_history(&mut self, index: usize) {
+        if let Some((value, cursor_pos)) = self.history.get(index) {
+            self.value = value.clone();
+            self.cursor_pos = *cursor_pos;
+            self.selection = None;
+            self.history_index = index;
+        }
+    }
+
+    pub fn undo(&mut self) {
+        if self.history_index > 0 {
+            self.restore_history(self.history_index - 1);
+        }
+    }
+
+    pub fn redo(&mut self) {
+        self.restore_history(self.history_index + 1);
+    }
+
+    /// the selected characters as an ordered range, `None` if nothing is selected
+    pub fn selected_range(&self) -> Option<(usize, usize)> {
+        self.selection
+            .map(|(anchor, head)| (anchor.min(head), anchor.max(head)))
+            .filter(|(start, end)| start != end)
+    }
+
+    pub fn selected_text(&self) -> Option<String> {
+        self.selected_range()
+            .map(|(start, end)| self.value[self.byte_index(start)..self.byte_index(end)].to_string())
+    }
+
+    /// moves the text cursor, extending the selection if `extend` is true and clearing it otherwise
+    pub fn move_cursor(&mut self, pos: usize, extend: bool) {
+        self.selection = if extend {
+            let anchor = self.selection.map(|(anchor, _)| anchor).unwrap_or(self.cursor_pos);
+            Some((anchor, pos))
+        } else {
+            None
+        };
+        self.cursor_pos = pos;
+    }
+
+    /// removes the selected characters and returns whether there was anything to remove
+    fn delete_selection(&mut self) -> bool {
+        let range = self.selected_range();
+        self.selection = None;
+        match range {
+            Some((start, end)) => {
+                let (start_index, end_index) = (self.byte_index(start), self.byte_index(end));
+                self.value.replace_range(start_index..end_index, "");
+                self.cursor_pos = start;
+                true
+            }
+            None => false
+        }
+    }
+
+    /// the byte offset of the character at `char_index`, or the length of the value if it is past the end
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(char_index)
+            .map(|(i, _)| i)
+            .unwrap_or(self.value.len())
+    }
+
+    /// replaces the selection with `c` and moves the text cursor behind it
+    fn insert(&mut self, c: char) {
+        self.delete_selection();
+        let index = self.byte_index(self.cursor_pos);
+        self.value.insert(index, c);
+        self.cursor_pos += 1;
+        self.record_history();
+    }
+
+    pub fn with_placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder = Some(placeholder.to_string());
+        self
+    }
+
+    /// the placeholder, if it is shown instead of the value
+    fn shown_placeholder(&self, active: bool) -> Option<&str> {
+        match &self.placeholder {
+            Some(placeholder) if !active && self.value.is_empty() => Some(placeholder),
+            _ => None
+        }
+    }
+}
+
+impl Default for TextInputState {
+    fn default() -> Self {
+        Self::new("")
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -26,16 +163,36 @@ pub struct TextInputStyle {
     pub background_color: Option<Color>,
     pub foreground_color: Color,
     pub padding: Padding,
-    pub min_width: f32
+    pub margin: Margin,
+    pub min_width: f32,
+    pub border: Option<Border>,
+    /// shows a bullet for every character instead of the value, e.g. for passwords
+    pub masked: bool,
+    pub focus: FocusStyle
+}
+
+impl TextInputStyle {
+    /// the text that gets shown for `value`
+    fn displayed(&self, value: &str) -> String {
+        if self.masked {
+            "\u{2022}".repeat(value.chars().count())
+        } else {
+            value.to_string()
+        }
+    }
 }
 
 impl Default for TextInputStyle {
     fn default() -> Self {
         Self {
-            background_color: None,
-            foreground_color: Color::BLACK,
+            background_color: InheritedStyle::current().background_color,
+            foreground_color: InheritedStyle::current().foreground_color.unwrap_or(Theme::current().foreground_color),
             padding: 0.0.into(),
-            min_width: 0.0
+            margin: 0.0.into(),
+            min_width: 0.0,
+            border: None,
+            masked: false,
+            focus: Default::default()
         }
     }
 }
@@ -46,7 +203,9 @@ impl Into<LabelStyle> for TextInputStyle {
             background_color: self.background_color,
             foreground_color: self.foreground_color,
             padding: self.padding,
+            margin: self.margin,
             min_width: self.min_width,
+            border: self.border,
             ..Default::default()
         }
     }
@@ -61,45 +220,141 @@ impl Into<TextInputStyle> for () {
 impl Renderer {
     pub fn text_input<T: Into<TextInputStyle>>(&mut self, id: u32, state: &mut TextInputState, style: T) {
         let style = style.into();
-        self.hitbox(id, move |r, hot, active| {
+        self.hitbox(id, move |r, hot, active, focused| {
+            if hot {
+                r.set_cursor_icon(CursorIcon::Text);
+            }
+            if let Some(placeholder) = state.shown_placeholder(active) {
+                let background_color = style.background_color.unwrap_or(r.background_color);
+                r.label(placeholder, LabelStyle {
+                    foreground_color: style.foreground_color.lerp(background_color, 0.5),
+                    ..style.into()
+                });
+                return;
+            }
             if hot || active { 
                 let (_, y) = r.pos();
-                let (_, height, text_end_x) = r.label(&state.value, style);
+                let displayed = style.displayed(&state.value);
+                let (_, height, text_end_x) = r.label(&displayed, style);
+                let text_x = text_end_x - r.calculate_text_size(&displayed).0;
 
                 if active {
                     let mut changed = false;
                     for c in r.consume_input() {
-                        if c.is_alphanumeric() || c.is_whitespace() {
-                            state.value.push(c);
+                        if !c.is_control() && r.has_glyph(c) {
+                            state.insert(c);
                         }
                         changed = true;
                     }
+                    let shift = r.is_shift();
+                    let ctrl = r.is_ctrl();
                     for k in r.consume_keys() {
+                        let len = state.value.chars().count();
                         match k {
-                            Key::Backspace => {
-                                state.value.pop();
-                                changed = true;
+                            Key::Z if ctrl && shift => state.redo(),
+                            Key::Z if ctrl => state.undo(),
+                            Key::Y if ctrl => state.redo(),
+                            Key::A if ctrl => {
+                                state.selection = Some((0, len));
+                                state.cursor_pos = len;
+                            },
+                            // masked values should not end up in the clipboard
+                            Key::C | Key::X if ctrl && style.masked => continue,
+                            Key::C if ctrl => {
+                                if let Some(selected) = state.selected_text() {
+                                    r.clipboard_set(&selected);
+                                }
+                                continue;
                             },
-                            _ => {}
+                            Key::X if ctrl => {
+                                if let Some(selected) = state.selected_text() {
+                                    r.clipboard_set(&selected);
+                                    state.delete_selection();
+                                }
+                            },
+                            Key::V if ctrl => {
+                                if let Some(text) = r.clipboard_get() {
+                                    state.delete_selection();
+                                    // pasted newlines would break the single line input
+                                    let text: String = text.chars().filter(|c| !c.is_control() && r.has_glyph(*c)).collect();
+                                    let index = state.byte_index(state.cursor_pos);
+                                    state.value.insert_str(index, &text);
+                                    state.cursor_pos += text.chars().count();
+                                }
+                            },
+                            Key::Backspace if state.delete_selection() => {},
+                            Key::Backspace if state.cursor_pos > 0 => {
+                                state.cursor_pos -= 1;
+                                let index = state.byte_index(state.cursor_pos);
+                                state.value.remove(index);
+                            },
+                            Key::Delete if state.delete_selection() => {},
+                            Key::Delete if state.cursor_pos < len => {
+                                let index = state.byte_index(state.cursor_pos);
+                                state.value.remove(index);
+                            },
+                            Key::Left => state.move_cursor(state.cursor_pos.saturating_sub(1), shift),
+                            Key::Right => state.move_cursor((state.cursor_pos + 1).min(len), shift),
+                            Key::Home => state.move_cursor(0, shift),
+                            Key::End => state.move_cursor(len, shift),
+                            _ => continue
                         }
+                        state.record_history();
+                        changed = true;
                     }
                     if changed {
                         state.last_typed_at = Local::now();
                     }
-                    let cursor_height = r.font.size as f32;
+                    let cursor_height = r.font().size as f32;
                     let cursor_width = 1.5;
 
+                    // the value might have been changed from the outside
+                    let len = state.value.chars().count();
+                    state.cursor_pos = state.cursor_pos.min(len);
+                    state.selection = state.selection.map(|(anchor, head)| (anchor.min(len), head.min(len)));
+
+                    let advance_widths = r.font().measure_text(&style.displayed(&state.value)).advance_widths;
+                    let offset = |index: usize| text_x + advance_widths[..index].iter().sum::<f32>();
+                    let cursor_y = y + style.margin.top + (height - cursor_height) / 2.0;
+
+                    if let Some((start, end)) = state.selected_range() {
+                        r.set_cursor_detached(offset(start), cursor_y, |r| {
+                            r.rectangle((offset(end) - offset(start), cursor_height), SELECTION_COLOR);
+                        });
+                    }
+
                     let current_millis = (Local::now() - state.last_typed_at).num_milliseconds() % 1000;
+                    // the cursor blinks every 500ms
+                    r.schedule_repaint_in(Duration::from_millis(500 - (current_millis % 500) as u64));
                     if current_millis < 500 {
-                        r.set_cursor(text_end_x + 2.0, y + (height - cursor_height) / 2.0, |r| {
-                            r.rectangle((cursor_width, cursor_height), Color::BLACK);
+                        r.set_cursor_detached(offset(state.cursor_pos) + 2.0, cursor_y, |r| {
+                            r.rectangle((cursor_width, cursor_height), style.foreground_color);
                         });
                     }
                 }
             } else { 
-                r.label(&state.value, style);
+                r.label(&style.displayed(&state.value), style);
             };
+            if focused {
+                r.draw_focus_ring(style.focus.color, style.focus.thickness);
+            }
         });
     }
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TextInputState;
+
+    #[test]
+    fn placeholder_is_hidden_once_a_character_is_typed() {
+        let mut state = TextInputState::new("").with_placeholder("Search");
+        assert_eq!(state.shown_placeholder(false), Some("Search"));
+        // the input is active while typing
+        assert_eq!(state.shown_placeholder(true), None);
+        state.insert('a');
+        assert_eq!(state.value, "a");
+        assert_eq!(state.shown_placeholder(false), None);
+    }
+}
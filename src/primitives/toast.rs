@@ -0,0 +1,86 @@
+use crate::animation::Transition;
+use crate::color::Color;
+use crate::styling::Padding;
+use crate::renderer::{Renderer, OVERLAY_LAYER};
+use crate::primitives::LabelStyle;
+
+use std::time::{Duration, Instant};
+
+/// how long fading a toast in and out takes
+const TOAST_FADE: Duration = Duration::from_millis(250);
+/// the space between the toasts and the bottom of the window
+const TOAST_MARGIN: f32 = 16.0;
+
+#[derive(Debug, Clone)]
+pub struct ToastState {
+    pub message: String,
+    pub shown_at: Instant,
+    /// how long the toast stays fully visible
+    pub duration: Duration
+}
+
+impl Renderer {
+    /// shows `message` at the bottom of the window for `duration_ms` milliseconds.
+    ///
+    /// Calling this again while the toast with the given id is still visible does nothing.
+    pub fn toast(&mut self, id: u32, message: &str, duration_ms: u64) {
+        if self.measuring || self.toast_state.contains_key(&id) {
+            return;
+        }
+        self.toast_state.insert(id, ToastState {
+            message: message.to_string(),
+            shown_at: Instant::now(),
+            duration: Duration::from_millis(duration_ms)
+        });
+    }
+
+    /// toasts are drawn after all the other widgets, stacked on top of each other with the newest one at the bottom
+    pub(crate) fn draw_toasts(&mut self) {
+        let mut toasts: Vec<(u32, ToastState)> = self.toast_state.iter().map(|(id, toast)| (*id, toast.clone())).collect();
+        toasts.sort_by_key(|(_, toast)| std::cmp::Reverse(toast.shown_at));
+
+        let mut y = self.height() - TOAST_MARGIN;
+        for (id, toast) in toasts {
+            let fade_in_id = self.id_from_str(&format!("{} toast fade in", id));
+            let fade_out_id = self.id_from_str(&format!("{} toast fade out", id));
+
+            let mut alpha = 1.0;
+            if toast.shown_at.elapsed() < TOAST_FADE + toast.duration {
+                self.schedule_repaint_in((TOAST_FADE + toast.duration).saturating_sub(toast.shown_at.elapsed()));
+                self.animate(fade_in_id, TOAST_FADE, &[Transition::EaseOut(0.0, 1.0)], |_, [value]| {
+                    alpha = value;
+                });
+            } else {
+                self.animate(fade_out_id, TOAST_FADE, &[Transition::Linear(1.0, 0.0)], |_, [value]| {
+                    alpha = value;
+                });
+            }
+            // the toast removes itself once it has faded out
+            if alpha <= 0.0 {
+                self.toast_state.remove(&id);
+                self.remove_animation(fade_in_id);
+                self.remove_animation(fade_out_id);
+                continue;
+            }
+
+            let style = LabelStyle {
+                background_color: Some(Color::rgba(50, 50, 50, (230.0 * alpha) as u16)),
+                foreground_color: Color::rgba(255, 255, 255, (255.0 * alpha) as u16),
+                padding: Padding::symmetric(8.0, 6.0),
+                border_radius: 4.0,
+                ..Default::default()
+            };
+            let (width, height) = self.measure_layout(|r| {
+                r.label(&toast.message, style);
+            });
+            y -= height;
+            let x = (self.width() - width) / 2.0;
+            self.with_layer(OVERLAY_LAYER, |r| {
+                r.set_cursor(x, y, |r| {
+                    r.label(&toast.message, style);
+                });
+            });
+            y -= TOAST_MARGIN / 2.0;
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::styling::Padding;
+use crate::theme::Theme;
+use crate::renderer::{Renderer, TOOLTIP_LAYER};
+use crate::primitives::LabelStyle;
+
+use std::time::Duration;
+
+/// how far away from the mouse the tooltip gets drawn
+const TOOLTIP_OFFSET: f32 = 12.0;
+
+pub struct TooltipStyle;
+
+impl Into<LabelStyle> for TooltipStyle {
+    fn into(self) -> LabelStyle {
+        LabelStyle {
+            background_color: Some(Theme::current().tooltip_background_color),
+            foreground_color: Theme::current().tooltip_foreground_color,
+            padding: Padding::uniform(4.0),
+            ..Default::default()
+        }
+    }
+}
+
+impl Renderer {
+    /// `id` has to be the id of the widget rendered inside `f`.
+    ///
+    /// The tooltip shows up once the widget has been hovered for `delay_ms` milliseconds.
+    pub fn tooltip(&mut self, id: u32, text: &str, delay_ms: u64, mut f: impl FnMut(&mut Self)) {
+        f(self);
+
+        let delay = Duration::from_millis(delay_ms);
+        let hovered_for = self.hover_start.get(&id).map(|start| start.elapsed());
+        let show = hovered_for.map(|hovered_for| hovered_for >= delay).unwrap_or(false);
+        if let Some(hovered_for) = hovered_for.filter(|_| !show) {
+            self.schedule_repaint_in(delay - hovered_for);
+        }
+
+        if show && !self.measuring {
+            let pos = (self.mouse.x as f32 + TOOLTIP_OFFSET, self.mouse.y as f32 + TOOLTIP_OFFSET);
+            self.tooltips.push((text.to_string(), pos));
+        }
+    }
+
+    /// tooltips are drawn after all the other widgets on their own layer so they never get covered
+    pub(crate) fn draw_tooltips(&mut self) {
+        for (text, (x, y)) in std::mem::take(&mut self.tooltips) {
+            let style: LabelStyle = TooltipStyle.into();
+            let (width, _) = self.calculate_text_size(&text);
+            let width = width + style.padding.left + style.padding.right;
+            let x = x.min(self.width() - width).max(0.0);
+            self.with_layer(TOOLTIP_LAYER, |r| {
+                r.set_cursor(x, y, |r| {
+                    r.label(&text, TooltipStyle);
+                });
+            });
+        }
+    }
+}
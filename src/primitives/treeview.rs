@@ -0,0 +1,42 @@
+use crate::styling::Padding;
+use crate::renderer::Renderer;
+use crate::primitives::{LabelStyle, ARROW_SIZE};
+
+/// how far the children of a tree node are indented
+const TREE_INDENT: f32 = 16.0;
+/// the space around the label of a tree node
+const NODE_PADDING: f32 = 2.0;
+
+impl Renderer {
+    /// draws a node which toggles `open` when clicked and its children rendered by `f` indented below it while `open` is true.
+    pub fn tree_node(&mut self, id: u32, label: &str, open: &mut bool, mut f: impl FnMut(&mut Self)) {
+        self.col(|r| {
+            r.hitbox(id, |r, hot, _active, _focused| {
+                let (x, y) = r.pos();
+                let (_, height, _) = r.label(label, LabelStyle {
+                    background_color: if hot { Some(r.theme().hover_color) } else { None },
+                    padding: Padding::sides(2.0 * NODE_PADDING + ARROW_SIZE, NODE_PADDING, NODE_PADDING, NODE_PADDING),
+                    ..Default::default()
+                });
+                r.draw_arrow(x + NODE_PADDING, y + height / 2.0, *open);
+            });
+            if r.clicked(id) {
+                *open = !*open;
+            }
+
+            if *open {
+                r.tree_indent_level += 1;
+                r.row(|r| {
+                    r.space(TREE_INDENT);
+                    r.col(|r| f(r));
+                });
+                r.tree_indent_level -= 1;
+            }
+        });
+    }
+
+    /// how many open tree nodes the widgets currently being rendered are nested in
+    pub fn tree_indent_level(&self) -> usize {
+        self.tree_indent_level
+    }
+}
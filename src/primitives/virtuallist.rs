@@ -0,0 +1,41 @@
+use crate::renderer::{Hitbox, Renderer};
+
+impl Renderer {
+    /// renders a list of `item_count` items which are all `item_height` high,
+    /// but only calls `render_item` for the items that are visible with the current `scroll_y`.
+    ///
+    /// The list takes up `visible_height` and scrolls with the mouse wheel while it is hovered.
+    pub fn virtual_list(
+        &mut self,
+        id: u32,
+        item_count: usize,
+        item_height: f32,
+        visible_height: f32,
+        scroll_y: &mut f32,
+        mut render_item: impl FnMut(&mut Self, usize),
+    ) {
+        let (x, y) = self.pos();
+        let visible_width = self.remaining_width();
+        let (mouse_x, mouse_y) = (self.mouse.x as f32, self.mouse.y as f32);
+
+        if !self.measuring && Hitbox::new(x, y, visible_width, visible_height).contains_pos(mouse_x, mouse_y) {
+            *scroll_y += self.mouse.scroll_delta;
+        }
+        let max_scroll = (item_count as f32 * item_height - visible_height).max(0.0);
+        *scroll_y = scroll_y.max(0.0).min(max_scroll);
+
+        let first = (*scroll_y / item_height).floor() as usize;
+        let last = (((*scroll_y + visible_height) / item_height).ceil() as usize).min(item_count);
+
+        self.hitbox(id, |r, _, _, _| {
+            r.begin_clip(x, y, visible_width, visible_height);
+            for i in first..last {
+                // every item is positioned as if all the items before it were rendered
+                let item_y = y + i as f32 * item_height - *scroll_y;
+                r.set_cursor_detached(x, item_y, |r| r.col(|r| render_item(r, i)));
+            }
+            r.end_clip();
+            r.handle_new_shape(visible_width, visible_height);
+        });
+    }
+}
@@ -1,31 +1,76 @@
 pub use winit::{
-    event::{Event, VirtualKeyCode, WindowEvent, ModifiersState},
+    event::{Event, VirtualKeyCode, WindowEvent, ModifiersState, MouseButton},
     event_loop::ControlFlow,
+    window::{CursorIcon, Fullscreen, WindowId},
 };
+use winit::{dpi::PhysicalSize, event_loop::EventLoop};
 
+use arboard::Clipboard;
+use cgmath::{Matrix4, SquareMatrix};
 use glium::{
-    index::PrimitiveType,
+    framebuffer::SimpleFrameBuffer,
+    glutin::ContextBuilder,
     DrawParameters,
     Blend,
-    texture::{CompressedSrgbTexture2d, RawImage2d, Texture2d},
-    uniform, Display, Frame, IndexBuffer, Program, Surface, VertexBuffer,
+    Rect,
+    texture::{RawImage2d, SrgbTexture2d, Texture2d},
+    uniform, Display, Frame, HeadlessRenderer, Program, ProgramCreationError, Surface,
 };
 
-use std::{collections::HashMap, convert::TryInto, time::{Duration, Instant}};
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    convert::TryInto,
+    hash::{Hash, Hasher},
+    path::Path,
+    rc::Rc,
+    time::{Duration, Instant},
+};
 
-use crate::animation::{Animation, Transition};
+use crate::animation::{Animation, PlaybackMode, Transition};
 use crate::color::Color;
-use crate::font::Font;
-use crate::key::Key;
-use crate::shaders::{FONT_VERTEX_SHADER, FONT_FRAGMENT_SHADER};
+use crate::backend::Backend;
+use crate::error::{HeadlessError, ImageLoadError, RendererError, ScreenshotError};
+use crate::font::{Font, FontStyle, FontVariants, FontWeight};
+use crate::gpu_pool::VBPool;
+use crate::key::{Key, KeyBinding, KeyCombo, KeyEvent};
+use crate::primitives::ToastState;
+use crate::styling::{InheritedStyle, StyleScope, StyleStack};
+use crate::theme::Theme;
+use crate::shaders::{FONT_VERTEX_SHADER, FONT_FRAGMENT_SHADER, FRAGMENT_SHADER, VERTEX_SHADER};
 use crate::vertex::{Vertex, FontVertex};
 
+/// how many triangles are used to approximate circles and ellipses
+pub const CIRCLE_SEGMENTS: usize = 32;
+/// how many triangles are used to approximate each corner of a rounded rectangle
+pub const ROUNDED_CORNER_SEGMENTS: usize = 8;
+/// the layer popups like modals and menus are drawn on
+pub const OVERLAY_LAYER: i32 = 100;
+/// tooltips are drawn above everything else
+pub const TOOLTIP_LAYER: i32 = 200;
+/// debug visualizations are drawn even above the tooltips
+pub const DEBUG_LAYER: i32 = 300;
+/// the font used when no other font is set through `with_font`
+const DEFAULT_FONT: &[u8] = include_bytes!("../font.ttf");
+/// how many frame times are kept for `fps_avg`, `fps_min` and `fps_max`
+const DEFAULT_FRAME_HISTORY_SIZE: usize = 60;
+/// how many vertices the buffers allocated up front can hold
+const GPU_POOL_CAPACITY: usize = 4096;
+/// generated ids start in the upper half of the id space so they don't collide with handpicked or hashed ones
+const GENERATED_ID_START: u32 = 1 << 31;
+
 #[derive(Debug, Copy, Clone)]
 pub struct MouseInfo {
     pub x: f64,
     pub y: f64,
     pub lmouseclick: bool,
-    pub rmouseclick: bool
+    pub rmouseclick: bool,
+    pub mmouseclick: bool,
+    /// true while the left mouse button is held down
+    pub lmousedown: bool,
+    /// how many pixels the mouse wheel scrolled down this frame
+    pub scroll_delta: f32
 }
 
 /// a hitbox is an area in the window that senses clicks/hovers/...
@@ -34,7 +79,11 @@ pub struct Hitbox {
     pub x: f32,
     pub y: f32,
     pub height: f32,
-    pub width: f32
+    pub width: f32,
+    /// the layer the hitbox was registered on, hitboxes on higher layers win
+    pub z: i32,
+    /// false for hitboxes that only block the mouse, e.g. the background of a panel, which tab navigation skips
+    pub focusable: bool
 }
 
 impl Hitbox {
@@ -43,7 +92,9 @@ impl Hitbox {
             x,
             y,
             height,
-            width
+            width,
+            z: 0,
+            focusable: true
         }
     }
 
@@ -55,86 +106,472 @@ impl Hitbox {
 
         (left <= x && x <= right) && (top <= y && y <= bottom)
     }
+
+    /// the area covered by both hitboxes, which has a size of zero if they don't overlap
+    pub fn intersect(&self, other: &Hitbox) -> Hitbox {
+        let left = self.x.max(other.x);
+        let top = self.y.max(other.y);
+        let right = (self.x + self.width).min(other.x + other.width);
+        let bottom = (self.y + self.height).min(other.y + other.height);
+
+        Hitbox {
+            z: self.z,
+            focusable: self.focusable,
+            ..Hitbox::new(left, top, (right - left).max(0.0), (bottom - top).max(0.0))
+        }
+    }
 }
 
+/// receives the payload of an event passed to `Renderer::emit`
+type EventListener = Box<dyn FnMut(Box<dyn Any>, &mut Renderer)>;
+
 pub struct Renderer {
-    /// this holds the current frame
-    frame: Frame,
+    /// this holds the current frame, `None` until the first frame starts
+    frame: Option<Frame>,
     /// how long the last frame took to render in nanoseconds
     frame_time: u32,
+    /// the frame times of the last `frame_history_size` frames in nanoseconds, oldest first
+    frame_time_history: Vec<u32>,
+    frame_history_size: usize,
     frame_start: Instant,
-    display: Display,
+    backend: Backend,
     program: Program,
     font_program: Program,
+    /// reused between draw calls, so the buffers don't have to be allocated every frame
+    vertex_pool: RefCell<VBPool<Vertex>>,
+    font_pool: RefCell<VBPool<FontVertex>>,
     pub modifiers: ModifiersState,
-    pub font: Font,
-    /// used for scaling the ui to the display
+    /// the font used outside of `with_font`
+    font: Font,
+    /// the fonts pushed by `with_font` together with the data they were loaded from, the last one is used for text
+    font_stack: Vec<(&'static [u8], Font)>,
+    /// used for scaling the ui to the display, in logical pixels
     viewport: (f32, f32),
-    cursor: (f32, f32),
+    /// how many physical pixels make up one logical pixel
+    scale_factor: f64,
+    pub(crate) cursor: (f32, f32),
     pub background_color: Color,
     layout_stack: Vec<Layout>,
+    /// the layers the renderer is currently inside, see `with_layer`
+    layer_stack: Vec<i32>,
+    /// the opacities the renderer is currently inside, see `with_alpha`
+    alpha_stack: Vec<f32>,
+    /// the transformations the renderer is currently inside, see `with_transform`
+    transform_stack: Vec<[[f32; 4]; 4]>,
+    /// the custom programs the renderer is currently inside, see `with_shader`
+    shader_stack: Vec<Rc<Program>>,
+    /// applied to the finished frame in order, see `add_post_effect`
+    post_effects: Vec<Program>,
+    /// the draw calls of the current frame, which get executed in `done`
+    draw_commands: Vec<DrawCommand>,
+    /// the depths of the layout stack at which new shapes don't affect the layout, see `set_cursor_detached`
+    detached_layouts: Vec<usize>,
+    /// the depth of a flex row with `Align::Stretch` and its height, labels directly inside of it grow to that height
+    stretch: Option<(usize, f32)>,
+    /// while measuring nothing gets drawn, no hitboxes get registered and no input gets consumed
+    pub(crate) measuring: bool,
     animations: HashMap<u32, Animation>,
+    /// when the first item of a staggered animation was rendered, keyed by the base id
+    stagger_start: HashMap<u32, Instant>,
+    /// the base ids of the staggered animations rendered this frame, the others get dropped from `stagger_start`
+    stagger_touched: HashSet<u32>,
     textures: HashMap<u32, Texture>,
+    /// internal state of widgets, unlike the hitboxes this survives between frames
+    widget_state: HashMap<u32, Box<dyn Any>>,
     /// holds the current mouse information
     pub mouse: MouseInfo,
     /// holds the characters that were input between the last render and current render
     pub input: Vec<char>,
     /// holds the virtual keys that were pressed between the last render and current render
-    pub keys: Vec<Key>,
+    pub keys: Vec<KeyEvent>,
+    /// the keys that are currently held down, used to tell repeated presses apart
+    pub(crate) held_keys: HashSet<Key>,
     pub active_id: Option<u32>,
     pub hot_id: Option<u32>,
+    /// the id that gets returned by the next call to `next_id`
+    generated_id: u32,
+    /// the widget that received focus through keyboard navigation
+    pub focus_id: Option<u32>,
     pub(crate) hitboxes: HashMap<u32, Hitbox>,
+    /// the ids of the hitboxes registered this frame, see `debug_id_collision_check`
+    #[cfg(debug_assertions)]
+    registered_ids: HashSet<u32>,
+    /// the ids a collision was already reported for, so every id only warns once
+    #[cfg(debug_assertions)]
+    reported_ids: HashSet<u32>,
+    /// the ids of the hitboxes in the order they were registered, used for tab navigation
+    pub(crate) focus_order: Vec<u32>,
     /// the hitboxes the renderer is currently inside
-    hitbox_stack: Vec<Hitbox>
+    hitbox_stack: Vec<Hitbox>,
+    /// the areas drawing is currently restricted to, each one already intersected with the previous one
+    clip_stack: Vec<Hitbox>,
+    /// when the currently hovered widget started being hovered
+    pub(crate) hover_start: HashMap<u32, Instant>,
+    /// tooltips that get drawn after the rest of the frame
+    pub(crate) tooltips: Vec<(String, (f32, f32))>,
+    /// `None` if the system clipboard is not available
+    clipboard: Option<Clipboard>,
+    /// the id of the next texture created by `render_to_texture`
+    generated_texture_id: u32,
+    /// draws the outlines of all the hitboxes at the end of every frame, toggled with F12
+    pub debug_mode: bool,
+    /// the earliest repaint requested through `schedule_repaint_in`
+    scheduled_repaint: Option<Instant>,
+    /// set through `request_redraw`, the event loop waits for events while this is false
+    needs_redraw: bool,
+    /// how many frames have been rendered
+    frame_count: u64,
+    /// the cursor icon requested this frame, see `set_cursor_icon`
+    cursor_icon: CursorIcon,
+    /// the cursor icon the window currently shows
+    applied_cursor_icon: CursorIcon,
+    cursor_hidden: bool,
+    /// set through `exit_with_code`, the event loop exits once this is set
+    exit_code: Option<i32>,
+    theme: Theme,
+    /// the styles inherited through `with_style`
+    style_stack: StyleStack,
+    /// fonts that were used before by the address of their data and their size,
+    /// so switching back doesn't have to render the glyphs again
+    font_cache: HashMap<(usize, u32), Font>,
+    /// the address and size of fonts that failed to load, so the error is only logged once
+    broken_fonts: HashSet<(usize, u32)>,
+    /// the bold and italic faces by the address of the data of the regular face, see `set_font_variants`
+    font_variants: HashMap<usize, FontVariants<'static>>,
+    /// the font `icon` draws with, set through `with_icon_font`
+    icon_font: Option<&'static [u8]>,
+    /// the loaded icon fonts by the address of their data and their size, they only contain the icons used so far
+    icon_fonts: HashMap<(usize, u32), Font>,
+    /// how many tree nodes the renderer is currently inside
+    pub(crate) tree_indent_level: usize,
+    /// the toasts that are currently shown
+    pub(crate) toast_state: HashMap<u32, ToastState>,
+    /// the indices of the open menus of the menu bar, starting with the top-level menu
+    pub(crate) open_menu_path: Vec<usize>,
+    /// the part of the window that is left after the panels of this frame, see `content_area`
+    pub(crate) content_area: (f32, f32, f32, f32),
+    /// the events passed to `emit` that haven't been dispatched yet
+    emitted_events: Vec<(u32, Box<dyn Any>)>,
+    /// the listeners registered through `on_event_fired` this frame
+    event_listeners: Vec<(u32, EventListener)>
 }
 
 impl Renderer {
-    pub fn new(display: Display, program: Program) -> Self {
-        let mut frame = display.draw();
-        frame.set_finish().unwrap();
-        let font = Font::from_memory(&display, include_bytes!("../font.ttf"), 18);
-        Self {
-            frame,
+    /// fails if the built-in shaders don't compile or the default font can't be loaded
+    pub fn new(display: Display, program: Program) -> Result<Self, RendererError> {
+        Self::from_backend(Backend::Window(display), program)
+    }
+
+    /// creates a renderer that draws to an off-screen context of `width` x `height` pixels instead of a window.
+    ///
+    /// Useful for tests, the output can be checked with `screenshot` or through the hitboxes.
+    pub fn headless(event_loop: &EventLoop<()>, width: u32, height: u32) -> Result<Self, HeadlessError> {
+        let context = ContextBuilder::new().build_headless(event_loop, PhysicalSize::new(width, height))?;
+        let renderer = HeadlessRenderer::new(context)?;
+        let program = Program::from_source(&renderer, VERTEX_SHADER, FRAGMENT_SHADER, None).map_err(RendererError::from)?;
+        Ok(Self::from_backend(Backend::Headless { renderer, size: (width, height) }, program)?)
+    }
+
+    fn from_backend(backend: Backend, program: Program) -> Result<Self, RendererError> {
+        let font = Font::from_memory(&backend, DEFAULT_FONT, 18).map_err(RendererError::FontLoad)?;
+        let font_program = Program::from_source(&backend, FONT_VERTEX_SHADER, FONT_FRAGMENT_SHADER, None)?;
+        let theme = Theme::light();
+        Ok(Self {
+            // the first frame gets acquired by `next_frame`
+            frame: None,
             modifiers: Default::default(),
-            background_color: Color::new(0, 0, 0),
+            background_color: theme.background_color,
             font,
+            font_stack: Vec::new(),
             keys: Vec::new(),
+            held_keys: HashSet::new(),
             mouse: MouseInfo {
                 x: 0.0,
                 y: 0.0,
                 lmouseclick: false,
                 rmouseclick: false,
+                mmouseclick: false,
+                lmousedown: false,
+                scroll_delta: 0.0,
             },
             input: Vec::new(),
-            font_program: Program::from_source(&display, FONT_VERTEX_SHADER, FONT_FRAGMENT_SHADER, None).unwrap(),
-            display,
+            font_program,
+            vertex_pool: RefCell::new(VBPool::with_capacity(&backend, 2, GPU_POOL_CAPACITY)),
+            font_pool: RefCell::new(VBPool::with_capacity(&backend, 1, GPU_POOL_CAPACITY)),
+            backend,
             frame_time: 0,
+            frame_time_history: Vec::new(),
+            frame_history_size: DEFAULT_FRAME_HISTORY_SIZE,
             frame_start: Instant::now(),
             program,
             viewport: (0.0, 0.0),
+            scale_factor: 1.0,
             cursor: (0.0, 0.0),
             layout_stack: vec![Layout::Col {
                 width: 0.0,
                 x: 0.0,
                 y: 0.0,
             }],
+            layer_stack: Vec::new(),
+            alpha_stack: Vec::new(),
+            transform_stack: Vec::new(),
+            shader_stack: Vec::new(),
+            post_effects: Vec::new(),
+            draw_commands: Vec::new(),
+            detached_layouts: Vec::new(),
+            stretch: None,
+            measuring: false,
             active_id: None,
             hot_id: None,
+            generated_id: GENERATED_ID_START,
+            focus_id: None,
             animations: HashMap::new(),
+            stagger_start: HashMap::new(),
+            stagger_touched: HashSet::new(),
             textures: HashMap::new(),
+            widget_state: HashMap::new(),
             hitboxes: HashMap::new(),
-            hitbox_stack: Vec::new()
-        }
+            #[cfg(debug_assertions)]
+            registered_ids: HashSet::new(),
+            #[cfg(debug_assertions)]
+            reported_ids: HashSet::new(),
+            focus_order: Vec::new(),
+            hitbox_stack: Vec::new(),
+            clip_stack: Vec::new(),
+            hover_start: HashMap::new(),
+            tooltips: Vec::new(),
+            clipboard: Clipboard::new().ok(),
+            generated_texture_id: GENERATED_ID_START,
+            debug_mode: false,
+            scheduled_repaint: None,
+            needs_redraw: true,
+            frame_count: 0,
+            cursor_icon: CursorIcon::Default,
+            applied_cursor_icon: CursorIcon::Default,
+            cursor_hidden: false,
+            exit_code: None,
+            theme,
+            style_stack: StyleStack::default(),
+            font_cache: HashMap::new(),
+            broken_fonts: HashSet::new(),
+            font_variants: HashMap::new(),
+            icon_font: None,
+            icon_fonts: HashMap::new(),
+            tree_indent_level: 0,
+            toast_state: HashMap::new(),
+            open_menu_path: Vec::new(),
+            content_area: (0.0, 0.0, 0.0, 0.0),
+            emitted_events: Vec::new(),
+            event_listeners: Vec::new()
+        })
+    }
+
+    /// the font text is currently drawn with
+    pub fn font(&self) -> &Font {
+        self.font_stack.last().map(|(_, font)| font).unwrap_or(&self.font)
+    }
+
+    /// returns true if the current font can draw `c`, text inputs drop every other character
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.font().get_info(c).is_some()
     }
 
+    /// changes the size of the current font, inside `with_font` this only affects the font passed to it
     pub fn change_font_size(&mut self, size: u32) {
-        self.font = Font::from_memory(&self.display, include_bytes!("../font.ttf"), size);
+        if size == self.font().size {
+            return;
+        }
+        let data = self.font_stack.last().map(|(data, _)| *data).unwrap_or(DEFAULT_FONT);
+        // the current font stays in use if the new size can't be loaded
+        let font = match self.load_font(data, size) {
+            Some(font) => font,
+            None => return,
+        };
+        let previous = match self.font_stack.last_mut() {
+            Some((_, current)) => std::mem::replace(current, font),
+            None => std::mem::replace(&mut self.font, font),
+        };
+        self.cache_font(data, previous);
+    }
+
+    /// text inside `f` is drawn with the font loaded from `data`
+    pub fn with_font(&mut self, data: &'static [u8], size: u32, mut f: impl FnMut(&mut Self)) {
+        // text inside `f` falls back to the current font if the font can't be loaded
+        let font = match self.load_font(data, size) {
+            Some(font) => font,
+            None => return f(self),
+        };
+        self.font_stack.push((data, font));
+        f(self);
+        if let Some((data, font)) = self.font_stack.pop() {
+            self.cache_font(data, font);
+        }
+    }
+
+    /// `icon` calls inside `f` draw with the font loaded from `data`
+    pub fn with_icon_font(&mut self, data: &'static [u8], mut f: impl FnMut(&mut Self)) {
+        let previous = self.icon_font.replace(data);
+        f(self);
+        self.icon_font = previous;
+    }
+
+    /// draws a single glyph of the icon font at `size` pixels, e.g. an icon mapped to the private use area.
+    ///
+    /// Outside of `with_icon_font` the glyph is taken from the default font. `size` is rounded to whole pixels,
+    /// every pixel size gets its own atlas that is kept around and only holds the glyphs drawn at that size so far.
+    pub fn icon(&mut self, glyph: char, size: f32, color: Color) {
+        let data = self.icon_font.unwrap_or(DEFAULT_FONT);
+        let key = (data.as_ptr() as usize, size.round().max(1.0) as u32);
+        if self.broken_fonts.contains(&key) {
+            return;
+        }
+        let mut font = match self.icon_fonts.remove(&key) {
+            Some(font) => font,
+            None => match Font::from_memory_with_chars(&self.backend, data, &[], key.1) {
+                Ok(font) => font,
+                Err(e) => {
+                    eprintln!("failed to load icon font: {}", e);
+                    self.broken_fonts.insert(key);
+                    return;
+                }
+            },
+        };
+        // glyphs that weren't drawn at this size before get added to the atlas of the font
+        if let Err(e) = font.add_glyph(&self.backend, data, glyph) {
+            eprintln!("failed to add the icon {:?}: {}", glyph, e);
+            self.icon_fonts.insert(key, font);
+            return;
+        }
+        self.font_stack.push((data, font));
+        self.text(&glyph.to_string(), color);
+        if let Some((_, font)) = self.font_stack.pop() {
+            self.icon_fonts.insert(key, font);
+        }
+    }
+
+    /// loads the bold and italic faces for the current font, e.g. the default font outside of `with_font`.
+    ///
+    /// If the faces can't be loaded the error gets logged and the font stays as it is.
+    pub fn set_font_variants(&mut self, variants: FontVariants<'static>) {
+        let data = self.font_stack.last().map(|(data, _)| *data).unwrap_or(DEFAULT_FONT);
+        let font = match Font::from_memory_with_variants(&self.backend, data, variants, self.font().size) {
+            Ok(font) => font,
+            Err(e) => {
+                eprintln!("failed to load font variants: {}", e);
+                return;
+            }
+        };
+        self.font_variants.insert(data.as_ptr() as usize, variants);
+        // the cached fonts were loaded without the variants
+        self.font_cache.retain(|(address, _), _| *address != data.as_ptr() as usize);
+        match self.font_stack.last_mut() {
+            Some((_, current)) => *current = font,
+            None => self.font = font,
+        }
+    }
+
+    /// returns the cached font or loads it, logging the error if `data` is not a valid font
+    fn load_font(&mut self, data: &'static [u8], size: u32) -> Option<Font> {
+        let address = data.as_ptr() as usize;
+        if let Some(font) = self.font_cache.remove(&(address, size)) {
+            return Some(font);
+        }
+        if self.broken_fonts.contains(&(address, size)) {
+            return None;
+        }
+        let variants = self.font_variants.get(&address).copied().unwrap_or_default();
+        match Font::from_memory_with_variants(&self.backend, data, variants, size) {
+            Ok(font) => Some(font),
+            Err(e) => {
+                eprintln!("failed to load font: {}", e);
+                self.broken_fonts.insert((address, size));
+                None
+            }
+        }
+    }
+
+    fn cache_font(&mut self, data: &'static [u8], font: Font) {
+        self.font_cache.insert((data.as_ptr() as usize, font.size), font);
+    }
+
+    /// widgets inside `f` that don't specify a foreground color, background color or font size inherit them from `style`.
+    ///
+    /// Nested calls only override the properties they specify.
+    pub fn with_style<S: StyleScope>(&mut self, style: S, mut f: impl FnMut(&mut Self)) {
+        let font_size = self.font().size;
+        self.style_stack.push(style);
+        self.apply_inherited_style();
+        f(self);
+        self.style_stack.pop();
+        self.apply_inherited_style();
+        self.change_font_size(font_size);
+    }
+
+    fn apply_inherited_style(&mut self) {
+        let style = self.style_stack.top();
+        if let Some(size) = style.font_size {
+            self.change_font_size(size);
+        }
+        InheritedStyle::set_current(style);
     }
 
     pub fn pos(&self) -> (f32, f32) {
         self.cursor
     }
 
+    /// returns a new id every time it is called, starting over every frame.
+    ///
+    /// The ids are only stable if the widgets are rendered in the same order every frame,
+    /// use `id_from_str` for widgets that are rendered conditionally.
+    pub fn next_id(&mut self) -> u32 {
+        let id = self.generated_id;
+        self.generated_id = self.generated_id.wrapping_add(1);
+        id
+    }
+
+    /// derives a stable id from a string by hashing it.
+    ///
+    /// The id is always in the lower half of the id space, so it can't collide with generated ids.
+    pub fn id_from_str(&self, s: &str) -> u32 {
+        let mut hasher = DefaultHasher::new();
+        s.hash(&mut hasher);
+        hasher.finish() as u32 & !GENERATED_ID_START
+    }
+
+    /// returns the state of the widget with the given id, inserting the default state if there is none yet.
+    ///
+    /// If the stored state has a different type it gets replaced.
+    pub fn get_state<T: Default + 'static>(&mut self, id: u32) -> &mut T {
+        let state = self
+            .widget_state
+            .entry(id)
+            .or_insert_with(|| Box::new(T::default()));
+        if !state.is::<T>() {
+            *state = Box::new(T::default());
+        }
+        state.downcast_mut::<T>().unwrap()
+    }
+
+    pub fn remove_state(&mut self, id: u32) {
+        self.widget_state.remove(&id);
+    }
+
+    pub fn clipboard_get(&mut self) -> Option<String> {
+        self.clipboard.as_mut().and_then(|clipboard| clipboard.get_text().ok())
+    }
+
+    pub fn clipboard_set(&mut self, s: &str) {
+        if let Some(clipboard) = self.clipboard.as_mut() {
+            let _ = clipboard.set_text(s.to_string());
+        }
+    }
+
+    /// returns true if the widget with the given id was clicked with the left mouse button this frame
+    pub fn clicked(&self, id: u32) -> bool {
+        // the measuring pass draws the same widgets again, which would report the click twice
+        !self.measuring && self.is_hot(id) && self.mouse.lmouseclick
+    }
+
     pub fn is_active(&self, id: u32) -> bool {
         self.active_id.map(|aid| aid == id).unwrap_or(false)
     }
@@ -143,8 +580,79 @@ impl Renderer {
         self.hot_id.map(|aid| aid == id).unwrap_or(false)
     }
 
+    /// a widget is focused if it is active and got there through keyboard navigation
+    pub fn is_focused(&self, id: u32) -> bool {
+        self.focus_id.map(|fid| fid == id).unwrap_or(false) && self.is_active(id)
+    }
+
+    /// draws the outline of every hitbox registered this frame
+    pub fn draw_debug_hitboxes(&mut self, color: Color) {
+        let hitboxes: Vec<Hitbox> = self.hitboxes.values().copied().collect();
+        let cursor = self.cursor;
+        self.with_layer(DEBUG_LAYER, |r| {
+            for hitbox in &hitboxes {
+                // `set_cursor` would treat negative positions as relative to the bottom right
+                r.cursor = (hitbox.x, hitbox.y);
+                r.draw_rectangle_outline((hitbox.width, hitbox.height), color, 1.0);
+            }
+        });
+        self.cursor = cursor;
+    }
+
+    pub(crate) fn update_hover_start(&mut self) {
+        match self.hot_id {
+            Some(id) => {
+                self.hover_start.retain(|hid, _| *hid == id);
+                self.hover_start.entry(id).or_insert_with(Instant::now);
+            }
+            None => self.hover_start.clear(),
+        }
+    }
+
     pub(crate) fn clear_hitboxes(&mut self) {
         self.hitboxes.clear();
+        self.focus_order.clear();
+        #[cfg(debug_assertions)]
+        self.registered_ids.clear();
+    }
+
+    /// warns if `id` was already used by another widget this frame, since both would share their state.
+    ///
+    /// Only checked in debug builds, every id is only reported once.
+    pub fn debug_id_collision_check(&mut self, id: u32) {
+        #[cfg(debug_assertions)]
+        if !self.registered_ids.insert(id) && self.reported_ids.insert(id) {
+            eprintln!("warning: the id {} is used by more than one widget in the same frame", id);
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = id;
+    }
+
+    /// moves the focus to the next (Tab) or previous (Shift+Tab) widget of the last frame
+    pub(crate) fn handle_focus_navigation(&mut self) {
+        let focusable: Vec<u32> = self
+            .focus_order
+            .iter()
+            .copied()
+            .filter(|id| self.hitboxes.get(id).map(|hitbox| hitbox.focusable).unwrap_or(true))
+            .collect();
+        if focusable.is_empty() || !self.is_key_pressed(Key::Tab) {
+            return;
+        }
+
+        let len = focusable.len();
+        let current = self
+            .active_id
+            .and_then(|id| focusable.iter().position(|fid| *fid == id));
+        let next = match current {
+            Some(idx) if self.is_shift() => (idx + len - 1) % len,
+            Some(idx) => (idx + 1) % len,
+            None if self.is_shift() => len - 1,
+            None => 0,
+        };
+
+        self.active_id = Some(focusable[next]);
+        self.focus_id = self.active_id;
     }
 
     pub fn fps(&self) -> u32 {
@@ -155,36 +663,183 @@ impl Renderer {
         1_000_000_000 / self.frame_time
     }
 
+    /// the average fps over the frame time history
+    pub fn fps_avg(&self) -> f32 {
+        if self.frame_time_history.is_empty() {
+            return 0.0;
+        }
+        let total: u64 = self.frame_time_history.iter().map(|time| *time as u64).sum();
+        let average = total as f32 / self.frame_time_history.len() as f32;
+        1_000_000_000.0 / average.max(1.0)
+    }
+
+    /// the fps of the slowest frame in the frame time history
+    pub fn fps_min(&self) -> f32 {
+        let slowest = self.frame_time_history.iter().max().copied().unwrap_or(0);
+        if slowest == 0 { 0.0 } else { 1_000_000_000.0 / slowest as f32 }
+    }
+
+    /// the fps of the fastest frame in the frame time history
+    pub fn fps_max(&self) -> f32 {
+        let fastest = self.frame_time_history.iter().min().copied().unwrap_or(0);
+        if fastest == 0 { 0.0 } else { 1_000_000_000.0 / fastest as f32 }
+    }
+
+    /// the frame times of the last frames in nanoseconds, oldest first
+    pub fn frame_time_history(&self) -> &[u32] {
+        &self.frame_time_history
+    }
+
+    /// how many frames are kept in the frame time history, 60 by default
+    pub fn set_frame_history_size(&mut self, size: usize) {
+        self.frame_history_size = size;
+        let excess = self.frame_time_history.len().saturating_sub(size);
+        self.frame_time_history.drain(..excess);
+    }
+
+    /// the projection works in logical pixels, the scaling to the physical size of the window
+    /// happens through the viewport of the frame
     fn projection_matrix(&self) -> [[f32; 4]; 4] {
         cgmath::ortho(0.0, self.viewport.0, self.viewport.1, 0.0, 0.0, 1.0).into()
     }
 
+    /// changes the default colors of all the widgets and the background color
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        self.background_color = theme.background_color;
+        Theme::set_current(theme);
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
     pub fn set_background_color(&mut self, color: Color) {
         self.background_color = color;
     }
 
     pub fn set_image(&mut self, id: u32, data: &[u8]) {
-        let image = {
-            let image = image::io::Reader::new(std::io::Cursor::new(data))
-                .with_guessed_format()
-                .unwrap()
-                .decode()
-                .unwrap()
-                .to_rgba8();
-            let image_dimensions = image.dimensions();
-            let image = RawImage2d::from_raw_rgba_reversed(&image.into_raw(), image_dimensions);
-            CompressedSrgbTexture2d::new(&self.display, image).unwrap()
+        self.set_image_rgba(id, data);
+    }
+
+    /// decodes the image and stores it as an uncompressed rgba texture, so transparency is kept
+    pub fn set_image_rgba(&mut self, id: u32, data: &[u8]) {
+        let image = Self::decode_image(data).unwrap();
+        self.insert_image(id, image);
+    }
+
+    /// loads an image from disk at runtime
+    pub fn set_image_from_path(&mut self, id: u32, path: &Path) -> Result<(), ImageLoadError> {
+        let data = std::fs::read(path)?;
+        let image = Self::decode_image(&data)?;
+        self.insert_image(id, image);
+        Ok(())
+    }
+
+    fn decode_image(data: &[u8]) -> Result<image::DynamicImage, ImageLoadError> {
+        let image = image::io::Reader::new(std::io::Cursor::new(data))
+            .with_guessed_format()?
+            .decode()?;
+        Ok(image)
+    }
+
+    fn insert_image(&mut self, id: u32, image: image::DynamicImage) {
+        let has_alpha = image.color().has_alpha();
+        let image = image.to_rgba8();
+        let image_dimensions = image.dimensions();
+        // this uses the `U8U8U8U8` client format
+        let image = RawImage2d::from_raw_rgba_reversed(&image.into_raw(), image_dimensions);
+        // the srgb variant keeps the colors the same as the compressed srgb textures used before
+        let texture = SrgbTexture2d::new(&self.backend, image).unwrap();
+
+        self.textures.insert(id, Texture::Image { texture, has_alpha });
+    }
+
+    pub fn remove_texture(&mut self, id: u32) {
+        self.textures.remove(&id);
+    }
+
+    /// renders the widgets in `f` into a new texture of the given size and returns the id of the texture.
+    ///
+    /// Every call creates a new texture, which is kept until it gets passed to `remove_texture`.
+    /// Use `render_into_texture` to render into the same texture every frame instead.
+    pub fn render_to_texture(&mut self, width: u32, height: u32, f: impl FnMut(&mut Self)) -> u32 {
+        let id = self.generated_texture_id;
+        self.generated_texture_id = self.generated_texture_id.wrapping_add(1);
+        self.render_into_texture(id, width, height, f);
+        id
+    }
+
+    /// renders the widgets in `f` into the texture with the given id, which gets replaced if it doesn't have the given size.
+    ///
+    /// The cursor starts at the top left of the texture. Hitboxes registered inside `f` are discarded,
+    /// since they don't match the position the texture gets drawn at.
+    pub fn render_into_texture(&mut self, id: u32, width: u32, height: u32, mut f: impl FnMut(&mut Self)) {
+        let draw_commands = std::mem::take(&mut self.draw_commands);
+        let clip_stack = std::mem::take(&mut self.clip_stack);
+        let viewport = self.viewport;
+        let scale_factor = self.scale_factor;
+        let cursor = self.cursor;
+        let content_area = self.content_area;
+        let focus_len = self.focus_order.len();
+
+        // one logical pixel is one texel inside the texture
+        self.viewport = (width as f32, height as f32);
+        self.content_area = (0.0, 0.0, width as f32, height as f32);
+        self.scale_factor = 1.0;
+        self.cursor = (0.0, 0.0);
+        self.layout_stack.push(Layout::Col { width: 0.0, x: 0.0, y: 0.0 });
+        f(self);
+        self.layout_stack.pop();
+
+        let commands = batch_draw_commands(std::mem::replace(&mut self.draw_commands, draw_commands));
+        let texture = match self.textures.remove(&id) {
+            Some(Texture::Image { texture, .. }) if texture.width() == width && texture.height() == height => texture,
+            _ => SrgbTexture2d::empty(&self.backend, width, height).unwrap(),
         };
+        {
+            let mut framebuffer = SimpleFrameBuffer::new(&self.backend, &texture).unwrap();
+            framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+            self.execute_draw_commands(&mut framebuffer, &commands);
+        }
+
+        self.clip_stack = clip_stack;
+        self.viewport = viewport;
+        self.scale_factor = scale_factor;
+        self.cursor = cursor;
+        self.content_area = content_area;
+        for hitbox_id in self.focus_order.drain(focus_len..) {
+            self.hitboxes.remove(&hitbox_id);
+        }
+
+        self.textures.insert(id, Texture::Image { texture, has_alpha: true });
+    }
 
-        self.textures.insert(id, Texture::Image(image));
+    /// the size of the texture in pixels
+    pub fn texture_size(&self, id: u32) -> Option<(u32, u32)> {
+        self.textures.get(&id).map(|texture| match texture {
+            Texture::Image { texture, .. } => (texture.width(), texture.height()),
+        })
     }
 
+    /// the height a label has to grow to, if it is placed directly inside a flex row with `Align::Stretch`
+    pub(crate) fn stretched_height(&self) -> Option<f32> {
+        match self.stretch {
+            Some((depth, height)) if depth == self.layout_stack.len() => Some(height),
+            _ => None,
+        }
+    }
+
+    /// the space between the cursor and the right edge of the content area, see `content_area`
     pub fn remaining_width(&self) -> f32 {
-        self.viewport.0 - self.cursor.0
+        let (x, _, width, _) = self.content_area;
+        x + width - self.cursor.0
     }
 
+    /// the space between the cursor and the bottom edge of the content area, see `content_area`
     pub fn remaining_height(&self) -> f32 {
-        self.viewport.1 - self.cursor.1
+        let (_, y, _, height) = self.content_area;
+        y + height - self.cursor.1
     }
 
     pub fn width(&self) -> f32 {
@@ -195,64 +850,347 @@ impl Renderer {
         self.viewport.1
     }
 
+    /// the size of the window in logical pixels
     pub fn get_viewport(&self) -> (f32, f32) {
-        let size = self.display.gl_window().window().inner_size();
-        (size.width as f32, size.height as f32)
+        let size = self.backend.inner_size().to_logical::<f32>(self.scale_factor);
+        (size.width, size.height)
     }
 
-    pub fn reset_cursor(&mut self) {
-        self.cursor = (0.0, 0.0);
+    /// `None` for headless renderers
+    pub fn window_id(&self) -> Option<WindowId> {
+        self.backend.with_window(|window| window.id())
+    }
+
+    /// changes the mouse cursor for the current frame, it goes back to the default cursor once nothing requests it anymore.
+    ///
+    /// The window only gets updated once the frame is done, so the last request of a frame wins.
+    pub fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        if !self.measuring {
+            self.cursor_icon = icon;
+        }
     }
 
-    fn setup_draw(&mut self, vertices: &[Vertex]) -> (VertexBuffer<Vertex>, IndexBuffer<u16>) {
-        let vb = VertexBuffer::new(&self.display, vertices).unwrap();
-        let ib = IndexBuffer::new(
-            &self.display,
-            PrimitiveType::TriangleStrip,
-            &(0..(vertices.len() as u16)).collect::<Vec<u16>>(),
-        )
-        .unwrap();
+    /// hides the mouse cursor while it is above the window
+    pub fn hide_cursor(&mut self, hidden: bool) {
+        self.backend.with_window(|window| window.set_cursor_visible(!hidden));
+        self.cursor_hidden = hidden;
+    }
 
-        (vb, ib)
+    pub fn is_cursor_hidden(&self) -> bool {
+        self.cursor_hidden
     }
 
-    fn draw_vertices(&mut self, vertices: &[Vertex]) {
-        let (vb, ib) = self.setup_draw(vertices);
+    /// exits the event loop, a non-zero code is returned as an error from `run_with_result`
+    pub fn exit_with_code(&mut self, code: i32) {
+        self.exit_code = Some(code);
+    }
 
-        let tex = Texture2d::empty(&self.display, 0, 0).unwrap();
-        let uniforms = uniform! {
-            use_texture: false,
-            tex: &tex,
-            projection: self.projection_matrix()
-        };
+    pub fn exit_code(&self) -> Option<i32> {
+        self.exit_code
+    }
+
+    /// does nothing for headless renderers
+    pub fn set_title(&mut self, title: &str) {
+        self.backend.with_window(|window| window.set_title(title));
+    }
+
+    pub fn set_fullscreen(&mut self, full: bool) {
+        let fullscreen = if full { Some(Fullscreen::Borderless(None)) } else { None };
+        self.backend.with_window(|window| window.set_fullscreen(fullscreen));
+    }
+
+    pub fn is_fullscreen(&self) -> bool {
+        self.backend.with_window(|window| window.fullscreen().is_some()).unwrap_or(false)
+    }
+
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    pub fn logical_to_physical(&self, x: f32) -> f32 {
+        x * self.scale_factor as f32
+    }
+
+    pub fn reset_cursor(&mut self) {
+        self.cursor = (0.0, 0.0);
+    }
 
-        self.frame
-            .draw(&vb, &ib, &self.program, &uniforms, &Default::default())
-            .unwrap();
+    fn draw_vertices(&mut self, vertices: &[Vertex]) {
+        self.draw_primitive(vertices, Triangles::Strip);
+    }
+
+    /// every shape gets turned into a list of triangles, so neighbouring shapes can be drawn in one draw call
+    pub(crate) fn draw_primitive(&mut self, vertices: &[Vertex], primitive: Triangles) {
+        self.push_draw_command(DrawKind::Shape {
+            vertices: vertices.to_vec(),
+            indices: triangulate(primitive, vertices.len()),
+        });
     }
 
     fn draw_texture(&mut self, size: (f32, f32), texture_id: u32) {
+        self.draw_texture_region(size, texture_id, (0.0, 0.0, 1.0, 1.0));
+    }
+
+    /// `uv_rect` is `(u_min, v_min, u_max, v_max)` where `(0, 0)` is the top left of the image
+    pub(crate) fn draw_texture_region(&mut self, size: (f32, f32), texture_id: u32, uv_rect: (f32, f32, f32, f32)) {
         let (x, y) = self.cursor;
         let (width, height) = size;
-        let vertices = &[
-            Vertex::textured((0.0, 1.0), x, y),
-            Vertex::textured((0.0, 0.0), x, y + height),
-            Vertex::textured((1.0, 1.0), x + width, y),
-            Vertex::textured((1.0, 0.0), x + width, y + height),
+        let (u_min, v_min, u_max, v_max) = uv_rect;
+        // images are stored upside down, so v has to be flipped
+        let vertices = vec![
+            Vertex::textured((u_min, 1.0 - v_min), x, y),
+            Vertex::textured((u_min, 1.0 - v_max), x, y + height),
+            Vertex::textured((u_max, 1.0 - v_min), x + width, y),
+            Vertex::textured((u_max, 1.0 - v_max), x + width, y + height),
         ];
-        let (vb, ib) = self.setup_draw(vertices);
-
-        match self.textures.get(&texture_id).expect("Texture not found") {
-            Texture::Image(tex) => {
-                let uniforms = uniform! {
-                    use_texture: true,
-                    projection: self.projection_matrix(),
-                    tex: tex,
-                };
+        self.push_draw_command(DrawKind::Texture { vertices, texture_id });
+    }
+
+    /// queues a draw call on the current layer, the draw calls get executed once the frame is done
+    fn push_draw_command(&mut self, mut kind: DrawKind) {
+        if self.measuring {
+            return;
+        }
+        let alpha = self.alpha();
+        if alpha < 1.0 {
+            match &mut kind {
+                DrawKind::Shape { vertices, .. } | DrawKind::Texture { vertices, .. } => {
+                    vertices.iter_mut().for_each(|vertex| vertex.multiply_alpha(alpha));
+                }
+                DrawKind::Glyph { vertices, .. } => {
+                    vertices.iter_mut().for_each(|vertex| vertex.color[3] *= alpha);
+                }
+            }
+        }
+        self.draw_commands.push(DrawCommand {
+            z: self.layer(),
+            scissor: self.scissor(),
+            transform: self.transform(),
+            program: self.shader_stack.last().cloned(),
+            kind,
+        });
+    }
 
-                self.frame
-                    .draw(&vb, &ib, &self.program, &uniforms, &Default::default())
+    /// the transformation new shapes get drawn with, the identity matrix outside of `with_transform`
+    pub fn transform(&self) -> [[f32; 4]; 4] {
+        self.transform_stack
+            .iter()
+            .fold(Matrix4::identity(), |transform, m| transform * Matrix4::from(*m))
+            .into()
+    }
+
+    /// everything drawn inside `f` is transformed by `m` without affecting the layout or the hitboxes,
+    /// nested calls multiply with each other.
+    ///
+    /// The matrix works in logical pixels, e.g. `Matrix4::from_translation` moves the shapes by that many pixels.
+    pub fn with_transform(&mut self, m: [[f32; 4]; 4], mut f: impl FnMut(&mut Self)) {
+        self.transform_stack.push(m);
+        f(self);
+        self.transform_stack.pop();
+    }
+
+    /// compiles a shader program which can be used with `with_shader`
+    pub fn compile_shader(&self, vert: &str, frag: &str) -> Result<Program, ProgramCreationError> {
+        Program::from_source(&self.backend, vert, frag, None)
+    }
+
+    /// shapes and images drawn inside `f` use `program` instead of the default shaders, text is not affected.
+    ///
+    /// The program gets the same vertex attributes and uniforms as the default shaders, see `shaders.rs`.
+    /// It is shared through an `Rc`, because the draw calls only get executed once the frame is done.
+    pub fn with_shader(&mut self, program: &Rc<Program>, mut f: impl FnMut(&mut Self)) {
+        self.shader_stack.push(Rc::clone(program));
+        f(self);
+        self.shader_stack.pop();
+    }
+
+    /// the opacity new shapes get drawn with
+    pub fn alpha(&self) -> f32 {
+        self.alpha_stack.iter().product()
+    }
+
+    /// everything drawn inside `f` has its opacity multiplied by `alpha`, nested calls multiply with each other
+    pub fn with_alpha(&mut self, alpha: f32, mut f: impl FnMut(&mut Self)) {
+        self.alpha_stack.push(alpha);
+        f(self);
+        self.alpha_stack.pop();
+    }
+
+    /// the layer new shapes get drawn on
+    pub fn layer(&self) -> i32 {
+        self.layer_stack.last().copied().unwrap_or(0)
+    }
+
+    /// everything drawn inside `f` ends up above everything drawn on lower layers, regardless of the order.
+    ///
+    /// The default layer is 0.
+    pub fn with_layer(&mut self, z: i32, mut f: impl FnMut(&mut Self)) {
+        self.layer_stack.push(z);
+        f(self);
+        self.layer_stack.pop();
+    }
+
+    /// executes the queued draw calls ordered by their layer
+    fn flush_draw_commands(&mut self) {
+        let commands = batch_draw_commands(std::mem::take(&mut self.draw_commands));
+        let mut frame = self.frame.take().expect("There is no frame to draw to");
+        if self.post_effects.is_empty() {
+            self.execute_draw_commands(&mut frame, &commands);
+        } else {
+            self.execute_post_effects(&mut frame, &commands);
+        }
+        self.frame = Some(frame);
+    }
+
+    /// adds a shader to the pipeline that gets applied to the finished frame, the effects run in the order they were added.
+    ///
+    /// The program gets the frame as the `tex` uniform, so it can use `VERTEX_SHADER` as its vertex shader.
+    pub fn add_post_effect(&mut self, shader: Program) {
+        self.post_effects.push(shader);
+    }
+
+    /// saves everything drawn so far this frame as a png, including the post effects.
+    ///
+    /// The draw calls only get executed once the frame is done, so this draws a copy of them into a texture
+    /// and reads it back instead of reading the frame itself.
+    pub fn screenshot(&self, path: &Path) -> Result<(), ScreenshotError> {
+        let (width, height) = self.frame.as_ref().ok_or(ScreenshotError::NoFrame)?.get_dimensions();
+        let commands = batch_draw_commands(self.draw_commands.clone());
+        let texture = SrgbTexture2d::empty(&self.backend, width, height).unwrap();
+        {
+            let mut framebuffer = SimpleFrameBuffer::new(&self.backend, &texture).unwrap();
+            if self.post_effects.is_empty() {
+                let c: [f32; 3] = self.background_color.into();
+                framebuffer.clear_color(c[0], c[1], c[2], 1.0);
+                self.execute_draw_commands(&mut framebuffer, &commands);
+            } else {
+                self.execute_post_effects(&mut framebuffer, &commands);
+            }
+        }
+
+        let pixels = texture.read_to_pixel_buffer().read()?;
+        let data = pixels.into_iter().flat_map(|(r, g, b, a)| vec![r, g, b, a]).collect();
+        let image = image::RgbaImage::from_raw(width, height, data).expect("The pixel buffer has the wrong size");
+        // opengl starts at the bottom left, images at the top left
+        image::imageops::flip_vertical(&image).save(path)?;
+        Ok(())
+    }
+
+    /// draws the commands into a texture and passes it through every post effect, the last one draws to `target`
+    fn execute_post_effects<S: Surface>(&self, target: &mut S, commands: &[DrawCommand]) {
+        let (width, height) = target.get_dimensions();
+        let c: [f32; 3] = self.background_color.into();
+        let mut source = SrgbTexture2d::empty(&self.backend, width, height).unwrap();
+        {
+            let mut framebuffer = SimpleFrameBuffer::new(&self.backend, &source).unwrap();
+            framebuffer.clear_color(c[0], c[1], c[2], 1.0);
+            self.execute_draw_commands(&mut framebuffer, commands);
+        }
+
+        // a quad covering the whole target, the projection maps it from (0, 0) to (1, 1)
+        let vertices = [
+            Vertex::textured((0.0, 0.0), 0.0, 0.0),
+            Vertex::textured((1.0, 0.0), 1.0, 0.0),
+            Vertex::textured((0.0, 1.0), 0.0, 1.0),
+            Vertex::textured((1.0, 1.0), 1.0, 1.0),
+        ];
+        let indices = triangulate(Triangles::Strip, vertices.len());
+        let buffers = self.vertex_pool.borrow_mut().acquire(&self.backend, &vertices, &indices);
+        let projection: [[f32; 4]; 4] = cgmath::ortho(0.0, 1.0, 0.0, 1.0, 0.0, 1.0).into();
+
+        for (i, effect) in self.post_effects.iter().enumerate() {
+            let uniforms = uniform! {
+                use_texture: true,
+                tex: &source,
+                projection: projection,
+            };
+            if i == self.post_effects.len() - 1 {
+                target
+                    .draw(buffers.vertices(), buffers.indices(), effect, &uniforms, &Default::default())
+                    .unwrap();
+            } else {
+                let target = SrgbTexture2d::empty(&self.backend, width, height).unwrap();
+                SimpleFrameBuffer::new(&self.backend, &target)
+                    .unwrap()
+                    .draw(buffers.vertices(), buffers.indices(), effect, &uniforms, &Default::default())
                     .unwrap();
+                source = target;
+            }
+        }
+        self.vertex_pool.borrow_mut().release(buffers);
+    }
+
+    fn execute_draw_commands<S: Surface>(&self, target: &mut S, commands: &[DrawCommand]) {
+        let projection = self.projection_matrix();
+        let empty = Texture2d::empty(&self.backend, 0, 0).unwrap();
+
+        for command in commands {
+            let projection: [[f32; 4]; 4] = (Matrix4::from(projection) * Matrix4::from(command.transform)).into();
+            let program = command.program.as_deref().unwrap_or(&self.program);
+            match &command.kind {
+                DrawKind::Shape { vertices, indices } => {
+                    let buffers = self.vertex_pool.borrow_mut().acquire(&self.backend, vertices, indices);
+                    let uniforms = uniform! {
+                        use_texture: false,
+                        tex: &empty,
+                        projection: projection
+                    };
+                    let draw_params = DrawParameters {
+                        blend: Blend::alpha_blending(),
+                        scissor: command.scissor,
+                        ..Default::default()
+                    };
+
+                    target
+                        .draw(buffers.vertices(), buffers.indices(), program, &uniforms, &draw_params)
+                        .unwrap();
+                    self.vertex_pool.borrow_mut().release(buffers);
+                }
+                DrawKind::Texture { vertices, texture_id } => {
+                    let indices = triangulate(Triangles::Strip, vertices.len());
+                    let buffers = self.vertex_pool.borrow_mut().acquire(&self.backend, vertices, &indices);
+                    match self.textures.get(texture_id).expect("Texture not found") {
+                        Texture::Image { texture, has_alpha } => {
+                            let uniforms = uniform! {
+                                use_texture: true,
+                                projection: projection,
+                                tex: texture,
+                            };
+                            let draw_params = DrawParameters {
+                                blend: if *has_alpha || vertices.iter().any(Vertex::is_translucent) {
+                                    Blend::alpha_blending()
+                                } else {
+                                    Default::default()
+                                },
+                                scissor: command.scissor,
+                                ..Default::default()
+                            };
+
+                            target
+                                .draw(buffers.vertices(), buffers.indices(), program, &uniforms, &draw_params)
+                                .unwrap();
+                        }
+                    }
+                    self.vertex_pool.borrow_mut().release(buffers);
+                }
+                DrawKind::Glyph { vertices, texture } => {
+                    let indices = triangulate(Triangles::List, vertices.len());
+                    let buffers = self.font_pool.borrow_mut().acquire(&self.backend, vertices, &indices);
+                    let uniforms = uniform! {
+                        tex: &**texture,
+                        projection: projection,
+                    };
+                    let draw_params = DrawParameters {
+                        blend: Blend::alpha_blending(),
+                        scissor: command.scissor,
+                        ..Default::default()
+                    };
+
+                    target
+                        .draw(buffers.vertices(), buffers.indices(), &self.font_program, &uniforms, &draw_params)
+                        .unwrap();
+                    self.font_pool.borrow_mut().release(buffers);
+                }
             }
         }
     }
@@ -269,6 +1207,48 @@ impl Renderer {
         self.cursor = cursor_copy;
     }
 
+    /// like `set_cursor`, but the shapes drawn inside `f` don't take up any space in the current layout.
+    /// Layouts started inside `f` work as usual.
+    ///
+    /// This is used by widgets that are made up of multiple shapes so they only report their total size once.
+    pub fn set_cursor_detached(&mut self, x: f32, y: f32, f: impl FnMut(&mut Self)) {
+        self.detached_layouts.push(self.layout_stack.len());
+        self.set_cursor(x, y, f);
+        self.detached_layouts.pop();
+    }
+
+    /// restricts drawing and hit detection to the given area until `end_clip` is called.
+    ///
+    /// Nested clips only allow drawing where they overlap with the outer clip.
+    pub fn begin_clip(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        let mut clip = Hitbox::new(x, y, width, height);
+        if let Some(outer) = self.clip_stack.last() {
+            clip = clip.intersect(outer);
+        }
+        self.clip_stack.push(clip);
+    }
+
+    pub fn end_clip(&mut self) {
+        self.clip_stack.pop();
+    }
+
+    /// the current clip area in framebuffer coordinates, which start at the bottom left
+    fn scissor(&self) -> Option<Rect> {
+        self.clip_stack.last().map(|clip| {
+            // the parts of the clip area outside of the window get cut off instead of moving it
+            let left = clip.x.max(0.0);
+            let bottom = (self.viewport.1 - clip.y - clip.height).max(0.0);
+            let right = clip.x + clip.width;
+            let top = self.viewport.1 - clip.y;
+            Rect {
+                left: self.logical_to_physical(left) as u32,
+                bottom: self.logical_to_physical(bottom) as u32,
+                width: self.logical_to_physical((right - left).max(0.0)) as u32,
+                height: self.logical_to_physical((top - bottom).max(0.0)) as u32,
+            }
+        })
+    }
+
     pub fn move_cursor(&mut self, x: f32, y: f32, mut f: impl FnMut(&mut Self)) {
         let cursor_copy = self.cursor;
         self.cursor.0 += x;
@@ -278,8 +1258,11 @@ impl Renderer {
     }
 
     pub fn clear(&mut self) {
-        let c: [f32; 3] = self.background_color.into();
-        self.frame.clear_color(c[0], c[1], c[2], 1.0);
+        // the alpha is kept so transparent windows can be see-through
+        let c: [f32; 4] = self.background_color.into();
+        if let Some(frame) = self.frame.as_mut() {
+            frame.clear_color(c[0], c[1], c[2], c[3]);
+        }
     }
 
     pub fn texture(&mut self, id: u32, size: (f32, f32)) {
@@ -288,127 +1271,276 @@ impl Renderer {
         self.handle_new_shape(width, height);
     }
 
+    /// draws part of a texture, e.g. a single sprite of a sprite sheet.
+    ///
+    /// `uv_rect` is `(u_min, v_min, u_max, v_max)` where `(0, 0)` is the top left of the image
+    pub fn texture_region(&mut self, id: u32, size: (f32, f32), uv_rect: (f32, f32, f32, f32)) {
+        let (width, height) = size;
+        self.draw_texture_region((width, height), id, uv_rect);
+        self.handle_new_shape(width, height);
+    }
+
     pub fn show_fps(&mut self) {
         self.set_cursor(-80.0, 0.0, |r| {
-            r.text(&format!("{:4} fps", r.fps()), Color::BLACK);
+            r.text(&format!("{:4} fps", r.fps()), r.theme().foreground_color);
         });
     }
 
-    pub fn calculate_text_size(&self, text: &str) -> (f32, f32) {
-        let mut x = 0.0;
-        let mut width = 0.0;
-        let height = self.font.size as f32;
-        let scale = 1.0;
-
-        for c in text.chars() {
-            let info = self.font.get_info(c).expect("The character is missing from the font");
-            let old_x = x;
-            x += ((info.advance >> 6) as f32) * scale; // bitshift by 6 to get value in pixels (2^6 = 64)
-            width += x - old_x;
+    /// like `show_fps`, but also draws a bar for every frame in the frame time history below it.
+    ///
+    /// A bar reaches the full height of the graph at 30 fps.
+    pub fn show_fps_graph(&mut self) {
+        self.show_fps();
+
+        let (graph_width, graph_height) = (80.0, 30.0);
+        let slow_frame = 1_000_000_000.0 / 30.0;
+        let bar_width = graph_width / self.frame_history_size.max(1) as f32;
+        let history = self.frame_time_history.clone();
+        let y = self.font().size as f32 + 4.0;
+        self.set_cursor_detached(-graph_width, y, |r| {
+            r.rectangle((graph_width, graph_height), Color::rgba(0, 0, 0, 60));
+        });
+        for (i, time) in history.iter().enumerate() {
+            let bar_height = (*time as f32 / slow_frame).min(1.0) * graph_height;
+            let x = self.width() - graph_width + i as f32 * bar_width;
+            self.set_cursor_detached(x, y + graph_height - bar_height, |r| {
+                r.rectangle((bar_width, bar_height), Color::new(0, 160, 0));
+            });
         }
+    }
 
-        (width, height)
+    pub fn calculate_text_size(&self, text: &str) -> (f32, f32) {
+        let metrics = self.font().measure_text(text);
+        (metrics.width, metrics.height)
     }
 
     pub fn text(&mut self, value: &str, color: Color) {
+        self.text_styled(value, color, FontWeight::Normal, FontStyle::Normal);
+    }
+
+    /// draws the text with the bold or italic face of the current font, see `set_font_variants`
+    pub fn text_styled(&mut self, value: &str, color: Color, weight: FontWeight, style: FontStyle) {
         let (mut x, y) = self.cursor;
         let mut width = 0.0;
         let mut height = 0.0;
         let scale = 1.0;
-
-        let draw_params = DrawParameters {
-            blend: Blend::alpha_blending(),
-            ..Default::default()
-        };
+        // all the glyphs share the atlas of the font, so the whole text is a single draw call
+        let mut vertices = Vec::with_capacity(value.len() * 6);
 
         for c in value.chars() {
-            let info = self.font.get_info(c).expect("The character is missing from the font");
+            let info = self.font().get_info_styled(c, weight, style).expect("The character is missing from the font");
             let xpos = x + info.bearing.0 as f32 * scale;
-            let ypos = y + (info.size.1 - info.bearing.1) as f32 * scale + (self.font.size as f32 - info.size.1 as f32) * scale;
+            let ypos = y + (info.size.1 - info.bearing.1) as f32 * scale + (self.font().size as f32 - info.size.1 as f32) * scale;
             let w = info.size.0 as f32 * scale;
             let h = info.size.1 as f32 * scale;
             if (ypos + h - y) > height {
                 height = ypos + h - y;
             }
-            let uniforms = uniform! {
-                tex: &info.texture,
-                projection: self.projection_matrix(),
-            };
-            let vertices = &[
+            let (u_min, v_min) = info.uv_min;
+            let (u_max, v_max) = info.uv_max;
+            vertices.extend_from_slice(&[
                 FontVertex {
                     position: [xpos, ypos + h],
-                    tex_pos: [0.0, 1.0],
+                    tex_pos: [u_min, v_max],
                     color: color.into()
                 },
                 FontVertex {
                     position: [xpos, ypos],
-                    tex_pos: [0.0, 0.0],
+                    tex_pos: [u_min, v_min],
                     color: color.into()
                 },
                 FontVertex {
                     position: [xpos + w, ypos],
-                    tex_pos: [1.0, 0.0],
+                    tex_pos: [u_max, v_min],
                     color: color.into()
                 },
                 FontVertex {
                     position: [xpos, ypos + h],
-                    tex_pos: [0.0, 1.0],
+                    tex_pos: [u_min, v_max],
                     color: color.into()
                 },
                 FontVertex {
                     position: [xpos + w, ypos],
-                    tex_pos: [1.0, 0.0],
+                    tex_pos: [u_max, v_min],
                     color: color.into()
                 },
                 FontVertex {
                     position: [xpos + w, ypos + h],
-                    tex_pos: [1.0, 1.0],
+                    tex_pos: [u_max, v_max],
                     color: color.into()
                 },
-            ];
+            ]);
             let old_x = x;
             // advance cursors for next glyph (note that advance is number of 1/64 pixels)
             x += ((info.advance >> 6) as f32) * scale; // bitshift by 6 to get value in pixels (2^6 = 64)
             width += x - old_x;
-            let vb = VertexBuffer::new(&self.display, vertices).unwrap();
-            let ib = IndexBuffer::new(
-                &self.display,
-                PrimitiveType::TriangleStrip,
-                &(0..6).collect::<Vec<u16>>(),
-            )
-            .unwrap();
-            self.frame
-                .draw(&vb, &ib, &self.font_program, &uniforms, &draw_params)
-                .unwrap();
+        }
+        if !vertices.is_empty() {
+            let texture = Rc::clone(&self.font().atlas);
+            self.push_draw_command(DrawKind::Glyph { vertices, texture });
         }
         self.handle_new_shape(width, height);
 
     }
 
     pub fn row(&mut self, mut f: impl FnMut(&mut Self) -> ()) {
+        let start = self.cursor;
         self.layout_stack.push(Layout::Row {
             height: 0.0,
             x: self.cursor.0,
             y: self.cursor.1,
         });
         f(self);
-        if let Layout::Row { height, x, y } = self.layout_stack.pop().unwrap() {
-            self.cursor.0 = x;
-            self.cursor.1 = y + height;
+        if let Some(layout @ Layout::Row { .. }) = self.layout_stack.pop() {
+            let (width, height) = layout.size(start, self.cursor);
+            self.cursor = start;
+            // the whole row is a single shape in the outer layout
+            self.handle_new_shape(width, height);
         }
     }
 
     pub fn col(&mut self, mut f: impl FnMut(&mut Self) -> ()) {
+        let start = self.cursor;
         self.layout_stack.push(Layout::Col {
             width: 0.0,
             x: self.cursor.0,
             y: self.cursor.1,
         });
         f(self);
-        if let Layout::Col { width, y, x } = self.layout_stack.pop().unwrap() {
-            self.cursor.0 = x + width;
-            self.cursor.1 = y;
+        if let Some(layout @ Layout::Col { .. }) = self.layout_stack.pop() {
+            let (width, height) = layout.size(start, self.cursor);
+            self.cursor = start;
+            // the whole column is a single shape in the outer layout
+            self.handle_new_shape(width, height);
+        }
+    }
+
+    /// lays out the widgets in `f` in a grid with `columns` equally wide columns,
+    /// which share the remaining width of the window
+    pub fn grid(&mut self, columns: usize, f: impl FnMut(&mut Self) -> ()) {
+        let width = self.remaining_width() / columns.max(1) as f32;
+        self.grid_with_widths(&vec![width; columns.max(1)], f);
+    }
+
+    /// lays out the widgets in `f` in a grid, moving on to the next row after every `widths.len()` widgets
+    pub fn grid_with_widths(&mut self, widths: &[f32], mut f: impl FnMut(&mut Self) -> ()) {
+        if widths.is_empty() {
+            return;
+        }
+        let start = self.cursor;
+        self.layout_stack.push(Layout::Grid {
+            widths: widths.to_vec(),
+            column: 0,
+            row_height: 0.0,
+            x: self.cursor.0,
+            y: self.cursor.1,
+        });
+        f(self);
+        if let Some(layout @ Layout::Grid { .. }) = self.layout_stack.pop() {
+            let (width, height) = layout.size(start, self.cursor);
+            self.cursor = start;
+            self.handle_new_shape(width, height);
+        }
+    }
+
+    /// centers content of the given size in the remaining space of the window
+    pub fn center(&mut self, width: f32, height: f32, mut f: impl FnMut(&mut Self)) {
+        let available_width = self.remaining_width();
+        let available_height = self.remaining_height();
+        self.center_horizontal(available_width, width, |r| {
+            r.center_vertical(available_height, height, &mut f);
+        });
+    }
+
+    /// centers content that is `content_width` wide in `available_width`.
+    ///
+    /// Inside a row the whole available width is taken up.
+    pub fn center_horizontal(&mut self, available_width: f32, content_width: f32, mut f: impl FnMut(&mut Self)) {
+        let offset = ((available_width - content_width) / 2.0).max(0.0);
+        self.cursor.0 += offset;
+        f(self);
+        self.cursor.0 -= offset;
+        if let Some(Layout::Row { .. }) = self.layout_stack.last() {
+            self.cursor.0 += (available_width - content_width).max(0.0);
+        }
+    }
+
+    /// centers content that is `content_height` high in `available_height`.
+    ///
+    /// Inside a column the whole available height is taken up.
+    pub fn center_vertical(&mut self, available_height: f32, content_height: f32, mut f: impl FnMut(&mut Self)) {
+        let offset = ((available_height - content_height) / 2.0).max(0.0);
+        self.cursor.1 += offset;
+        f(self);
+        self.cursor.1 -= offset;
+        if let Some(Layout::Col { .. }) = self.layout_stack.last() {
+            self.cursor.1 += (available_height - content_height).max(0.0);
+        }
+    }
+
+    /// lays out the widgets in `f` in a row that spans the remaining width of the window.
+    ///
+    /// `f` gets called twice, once to measure the widgets and once to actually draw them.
+    pub fn flex_row(&mut self, justify: Justify, align: Align, mut f: impl FnMut(&mut Self)) {
+        let (x, y) = self.cursor;
+        let available_width = self.remaining_width();
+
+        self.layout_stack.push(Layout::Flex {
+            sizes: Vec::new(),
+            positions: Vec::new(),
+            index: 0,
+        });
+        self.with_measuring(|r| f(r));
+        let sizes = match self.layout_stack.pop() {
+            Some(Layout::Flex { sizes, .. }) => sizes,
+            _ => unreachable!(),
+        };
+        if sizes.is_empty() {
+            return;
+        }
+
+        let count = sizes.len() as f32;
+        let free = (available_width - sizes.iter().map(|(w, _)| w).sum::<f32>()).max(0.0);
+        let height = sizes.iter().map(|(_, h)| *h).fold(0.0, f32::max);
+        let (start, gap) = match justify {
+            Justify::Start => (0.0, 0.0),
+            Justify::End => (free, 0.0),
+            Justify::Center => (free / 2.0, 0.0),
+            Justify::SpaceBetween if sizes.len() > 1 => (0.0, free / (count - 1.0)),
+            Justify::SpaceBetween => (0.0, 0.0),
+            Justify::SpaceAround => (free / count / 2.0, free / count),
+        };
+
+        let mut item_x = x + start;
+        let positions = sizes
+            .iter()
+            .map(|(w, h)| {
+                let item_y = match align {
+                    Align::Start | Align::Stretch => y,
+                    Align::Center => y + (height - h) / 2.0,
+                    Align::End => y + height - h,
+                };
+                let pos = (item_x, item_y);
+                item_x += w + gap;
+                pos
+            })
+            .collect::<Vec<_>>();
+
+        self.cursor = positions[0];
+        self.layout_stack.push(Layout::Flex {
+            sizes: Vec::new(),
+            positions,
+            index: 0,
+        });
+        let stretch = self.stretch.take();
+        if align == Align::Stretch {
+            self.stretch = Some((self.layout_stack.len(), height));
         }
+        f(self);
+        self.stretch = stretch;
+        self.layout_stack.pop();
+        self.cursor = (x, y);
+        self.handle_new_shape(available_width, height);
     }
 
     pub fn animate<const N: usize>(
@@ -416,51 +1548,122 @@ impl Renderer {
         id: u32,
         duration: Duration,
         transitions: &[Transition; N],
+        f: impl FnMut(&mut Self, [f32; N]) -> (),
+    ) {
+        self.animate_with_mode(id, duration, PlaybackMode::Once, transitions, f);
+    }
+
+    pub fn animate_with_mode<const N: usize>(
+        &mut self,
+        id: u32,
+        duration: Duration,
+        mode: PlaybackMode,
+        transitions: &[Transition; N],
         mut f: impl FnMut(&mut Self, [f32; N]) -> (),
     ) {
         let result = match self.animations.get_mut(&id) {
-            Some(animation) => animation.animate(),
+            Some(animation) => {
+                if animation.mode() != mode {
+                    animation.set_mode(mode);
+                }
+                animation.animate()
+            }
             None => {
                 let mut animation = Animation::new(duration, transitions.to_vec());
+                animation.set_mode(mode);
                 let result = animation.animate();
                 self.animations.insert(id, animation);
                 result
             }
         };
 
+        if self.animations.get(&id).map_or(false, Animation::is_running) {
+            self.request_redraw();
+        }
         f(self, result.try_into().unwrap());
     }
 
+    /// animates the item at `index` of a list with the id `base_id + index`.
+    ///
+    /// Each item only starts animating once `index * delay` has passed since `base_id` was first rendered,
+    /// until then `f` receives the start values of the transitions.
+    pub fn animate_staggered<const N: usize>(
+        &mut self,
+        base_id: u32,
+        index: usize,
+        delay: Duration,
+        duration: Duration,
+        transitions: &[Transition; N],
+        mut f: impl FnMut(&mut Self, [f32; N]) -> (),
+    ) {
+        let item_id = base_id + index as u32;
+        let start = *self.stagger_start.entry(base_id).or_insert(self.frame_start);
+        self.stagger_touched.insert(base_id);
+        if start == self.frame_start {
+            // the list (re)appeared this frame, so the items animate from the start again
+            self.animations.remove(&item_id);
+        }
+
+        let item_delay = delay * index as u32;
+        if start.elapsed() < item_delay {
+            self.schedule_repaint_in(item_delay.saturating_sub(start.elapsed()));
+            let mut result = [0.0; N];
+            for (value, transition) in result.iter_mut().zip(transitions.iter()) {
+                *value = transition.calculate(0.0);
+            }
+            f(self, result);
+        } else {
+            self.animate(item_id, duration, transitions, f);
+        }
+    }
+
+    /// returns the topmost hitbox at the given position.
+    ///
+    /// Hitboxes on higher layers win, otherwise the one registered last wins.
     pub fn get_hit(&self, x: f32, y: f32) -> Option<u32> {
-        self.hitboxes
+        self.focus_order
             .iter()
-            .find(|(_, hb)| hb.contains_pos(x, y))
-            .map(|(id, _)| *id)
+            .filter_map(|id| self.hitboxes.get(id).map(|hb| (*id, hb)))
+            .filter(|(_, hb)| hb.contains_pos(x, y))
+            .max_by_key(|(_, hb)| hb.z)
+            .map(|(id, _)| id)
     }
 
-    pub fn hitbox(&mut self, id: u32, mut f: impl FnMut(&mut Self, bool, bool) -> ()) {
+    pub fn hitbox(&mut self, id: u32, mut f: impl FnMut(&mut Self, bool, bool, bool) -> ()) {
         let is_hot = self.is_hot(id);
         let is_active = self.is_active(id);
+        let is_focused = self.is_focused(id);
+        if !self.measuring {
+            self.debug_id_collision_check(id);
+            self.focus_order.push(id);
+        }
         self.hitbox_stack.push(Hitbox::new(self.cursor.0, self.cursor.1, 0.0, 0.0));
-        f(self, is_hot, is_active);
-        self.hitboxes.insert(id, self.hitbox_stack.pop().unwrap());
+        f(self, is_hot, is_active, is_focused);
+        let mut hitbox = self.hitbox_stack.pop().unwrap();
+        if let Some(clip) = self.clip_stack.last() {
+            hitbox = hitbox.intersect(clip);
+        }
+        hitbox.z = self.layer();
+        if !self.measuring {
+            self.hitboxes.insert(id, hitbox);
+        }
     }
 
-    fn handle_new_shape(&mut self, shape_width: f32, shape_height: f32) {
-        match self.layout_stack.iter_mut().last().unwrap() {
-            Layout::Row { height, .. } => {
-                self.cursor.0 += shape_width;
-                if shape_height > *height {
-                    *height = shape_height;
-                }
-            }
-            Layout::Col { width, .. } => {
-                self.cursor.1 += shape_height;
-                if shape_width > *width {
-                    *width = shape_width;
-                }
-            }
-        };
+    /// draws a border around the current hitbox, meant to be called at the end of a `hitbox` closure while it is focused
+    pub fn draw_focus_ring(&mut self, color: Color, thickness: f32) {
+        if let Some(hitbox) = self.hitbox_stack.last().copied() {
+            let cursor = self.cursor;
+            self.cursor = (hitbox.x, hitbox.y);
+            self.draw_rectangle_outline((hitbox.width, hitbox.height), color, thickness);
+            self.cursor = cursor;
+        }
+    }
+
+    pub(crate) fn handle_new_shape(&mut self, shape_width: f32, shape_height: f32) {
+        // shapes drawn detached from the current layout only grow the hitbox
+        if self.detached_layouts.last() != Some(&self.layout_stack.len()) {
+            self.advance_layout(shape_width, shape_height);
+        }
 
         if let Some(hitbox) = self.hitbox_stack.iter_mut().last() {
             let width = shape_width;//self.cursor.0 - hitbox.x;
@@ -476,19 +1679,54 @@ impl Renderer {
         }
     }
 
+    fn advance_layout(&mut self, shape_width: f32, shape_height: f32) {
+        self.layout_stack
+            .last_mut()
+            .unwrap()
+            .advance(&mut self.cursor, shape_width, shape_height);
+    }
+
+    pub fn remove_animation(&mut self, id: u32) {
+        self.animations.remove(&id);
+    }
+
     pub fn reset_animation(&mut self, id: u32) {
         if let Some(animation) = self.animations.get_mut(&id) {
             animation.reset();
         }
     }
 
+    pub fn pause_animation(&mut self, id: u32) {
+        if let Some(animation) = self.animations.get_mut(&id) {
+            animation.pause();
+        }
+    }
+
+    pub fn resume_animation(&mut self, id: u32) {
+        if let Some(animation) = self.animations.get_mut(&id) {
+            animation.resume();
+        }
+    }
+
     pub fn space(&mut self, size: f32) {
         match self.layout_stack.iter().last().unwrap() {
-            Layout::Row { .. } => self.cursor.0 += size,
+            Layout::Row { .. } | Layout::Grid { .. } => self.cursor.0 += size,
+            // spaces inside a flex row are items of their own so they survive the positioning
+            Layout::Flex { .. } => self.handle_new_shape(size, 0.0),
             Layout::Col { .. } => self.cursor.1 += size,
         }
     }
 
+    /// renders the items with a `space(gap)` between each of them, but not before the first or after the last one
+    pub fn space_between(&mut self, gap: f32, items: &[impl Fn(&mut Self)]) {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.space(gap);
+            }
+            item(self);
+        }
+    }
+
     pub fn rectangle(&mut self, size: (f32, f32), color: Color) {
         let (width, height) = size;
         let (x, y) = self.cursor;
@@ -505,34 +1743,514 @@ impl Renderer {
         self.handle_new_shape(width, height);
     }
 
+    /// draws a circle around the given center, which is relative to the cursor
+    pub fn draw_circle(&mut self, center_x: f32, center_y: f32, radius: f32, color: Color) {
+        let (x, y) = self.cursor;
+        self.draw_ellipse_at((x + center_x, y + center_y), (radius, radius), color);
+    }
+
+    /// draws an ellipse centered on the cursor
+    pub fn draw_ellipse(&mut self, rx: f32, ry: f32, color: Color) {
+        let center = self.cursor;
+        self.draw_ellipse_at(center, (rx, ry), color);
+    }
+
+    /// draws a line between two points relative to the cursor without affecting the layout
+    pub fn draw_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+        let (x, y) = self.cursor;
+        let (dx, dy) = (x2 - x1, y2 - y1);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length == 0.0 {
+            return;
+        }
+        // the normal of the line scaled to half the thickness
+        let nx = -dy / length * thickness / 2.0;
+        let ny = dx / length * thickness / 2.0;
+        let (x1, y1, x2, y2) = (x + x1, y + y1, x + x2, y + y2);
+
+        self.draw_vertices(
+            &[
+                Vertex::colored(color, x1 + nx, y1 + ny),
+                Vertex::colored(color, x1 - nx, y1 - ny),
+                Vertex::colored(color, x2 + nx, y2 + ny),
+                Vertex::colored(color, x2 - nx, y2 - ny),
+            ]
+        );
+    }
+
+    fn draw_ellipse_at(&mut self, center: (f32, f32), radius: (f32, f32), color: Color) {
+        let (cx, cy) = center;
+        let (rx, ry) = radius;
+        let mut vertices = Vec::with_capacity(CIRCLE_SEGMENTS + 2);
+        vertices.push(Vertex::colored(color, cx, cy));
+        for i in 0..=CIRCLE_SEGMENTS {
+            let angle = i as f32 / CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+            vertices.push(Vertex::colored(color, cx + rx * angle.cos(), cy + ry * angle.sin()));
+        }
+
+        self.draw_primitive(&vertices, Triangles::Fan);
+    }
+
+    /// draws the outline of a rectangle at the cursor without affecting the layout.
+    ///
+    /// The outline is drawn on the inside of the rectangle.
+    pub fn draw_rectangle_outline(&mut self, size: (f32, f32), color: Color, thickness: f32) {
+        let (width, height) = size;
+        let (x, y) = self.cursor;
+        let thickness = thickness.min(width / 2.0).min(height / 2.0);
+
+        // top, bottom, left, right
+        let edges = [
+            (x, y, width, thickness),
+            (x, y + height - thickness, width, thickness),
+            (x, y + thickness, thickness, height - 2.0 * thickness),
+            (x + width - thickness, y + thickness, thickness, height - 2.0 * thickness),
+        ];
+
+        for (x, y, width, height) in edges.iter().copied() {
+            self.draw_vertices(
+                &[
+                    Vertex::colored(color, x, y),
+                    Vertex::colored(color, x, y + height),
+                    Vertex::colored(color, x + width, y),
+                    Vertex::colored(color, x + width, y + height),
+                ]
+            );
+        }
+    }
+
+    pub fn draw_rounded_rect(&mut self, size: (f32, f32), radius: f32, color: Color) {
+        let (width, height) = size;
+        let (x, y) = self.cursor;
+        let radius = radius.min(width / 2.0).min(height / 2.0).max(0.0);
+
+        // the centers of the corner arcs and the angle each arc starts at, clockwise from the top left
+        let corners = [
+            (x + radius, y + radius, 180.0f32),
+            (x + width - radius, y + radius, 270.0),
+            (x + width - radius, y + height - radius, 0.0),
+            (x + radius, y + height - radius, 90.0),
+        ];
+
+        let mut vertices = Vec::with_capacity(4 * (ROUNDED_CORNER_SEGMENTS + 1) + 2);
+        vertices.push(Vertex::colored(color, x + width / 2.0, y + height / 2.0));
+        for (cx, cy, start) in corners.iter() {
+            for i in 0..=ROUNDED_CORNER_SEGMENTS {
+                let angle = (start + 90.0 * i as f32 / ROUNDED_CORNER_SEGMENTS as f32).to_radians();
+                vertices.push(Vertex::colored(color, cx + radius * angle.cos(), cy + radius * angle.sin()));
+            }
+        }
+        // close the fan
+        vertices.push(vertices[1]);
+
+        self.draw_primitive(&vertices, Triangles::Fan);
+        self.handle_new_shape(width, height);
+    }
+
     pub fn consume_input(&mut self) -> Vec<char> {
+        if self.measuring {
+            return Vec::new();
+        }
         std::mem::take(&mut self.input)
     }
 
     pub fn consume_keys(&mut self) -> Vec<Key> {
-        std::mem::take(&mut self.keys)
+        if self.measuring {
+            return Vec::new();
+        }
+        std::mem::take(&mut self.keys).into_iter().map(|event| event.key).collect()
+    }
+
+    /// returns true if `key` was pressed this frame, including repeated presses
+    pub fn is_key_pressed(&self, key: Key) -> bool {
+        self.keys.iter().any(|event| event.key == key)
+    }
+
+    /// the size the widgets in `f` would take up inside a column, without drawing them.
+    ///
+    /// Nothing inside `f` gets drawn, registers a hitbox or consumes input, e.g. to center content before drawing it.
+    pub fn measure_layout(&mut self, mut f: impl FnMut(&mut Self)) -> (f32, f32) {
+        let (x, y) = self.cursor;
+        let mut size = (0.0, 0.0);
+        self.with_measuring(|r| {
+            r.layout_stack.push(Layout::Col { width: 0.0, x, y });
+            f(r);
+            if let Some(Layout::Col { width, .. }) = r.layout_stack.pop() {
+                size = (width, r.cursor.1 - y);
+            }
+        });
+        size
+    }
+
+    /// runs `f` without drawing anything, so only the layout gets computed
+    pub(crate) fn with_measuring(&mut self, mut f: impl FnMut(&mut Self)) {
+        let was_measuring = self.measuring;
+        let cursor_copy = self.cursor;
+        self.measuring = true;
+        f(self);
+        self.measuring = was_measuring;
+        self.cursor = cursor_copy;
+    }
+
+    pub fn is_ctrl(&self) -> bool {
+        self.modifiers.ctrl()
+    }
+
+    pub fn is_shift(&self) -> bool {
+        self.modifiers.shift()
+    }
+
+    pub fn is_alt(&self) -> bool {
+        self.modifiers.alt()
+    }
+
+    /// the windows key on windows and the command key on macos
+    pub fn is_super(&self) -> bool {
+        self.modifiers.logo()
+    }
+
+    /// returns true if the key of the combo was pressed this frame while the required modifiers were held, see `KeyCombo::matches`
+    pub fn key_combo_pressed<T: Into<KeyCombo>>(&self, combo: T) -> bool {
+        let keys: Vec<Key> = self.keys.iter().map(|event| event.key).collect();
+        combo.into().matches(&keys, self.modifiers)
+    }
+
+    /// returns true if the key of the binding was pressed this frame while exactly the required modifiers were held
+    pub fn is_key_combo_pressed(&self, binding: KeyBinding) -> bool {
+        self.key_combo_pressed(binding)
+    }
+
+    /// makes sure the next frame gets rendered after `d` at the latest, the earliest request wins.
+    ///
+    /// While a repaint is scheduled the event loop waits for it instead of rendering continuously.
+    pub fn schedule_repaint_in(&mut self, d: Duration) {
+        let at = Instant::now() + d;
+        self.scheduled_repaint = Some(self.scheduled_repaint.map_or(at, |scheduled| scheduled.min(at)));
+    }
+
+    pub(crate) fn scheduled_repaint(&self) -> Option<Instant> {
+        self.scheduled_repaint
+    }
+
+    /// queues an event that gets passed to the listener of `event_id` at the end of the frame, see `on_event_fired`
+    pub fn emit(&mut self, event_id: u32, payload: Box<dyn Any>) {
+        if !self.measuring {
+            self.emitted_events.push((event_id, payload));
+        }
+    }
+
+    /// registers `f` as the listener of `event_id` for this frame.
+    ///
+    /// Every event is passed to the first listener registered for its id, events without a listener are dropped.
+    pub fn on_event_fired(&mut self, event_id: u32, f: impl FnMut(Box<dyn Any>, &mut Renderer) + 'static) {
+        if !self.measuring {
+            self.event_listeners.push((event_id, Box::new(f)));
+        }
+    }
+
+    /// passes the emitted events to their listeners, events emitted by the listeners are dispatched in the next frame
+    pub(crate) fn dispatch_events(&mut self) {
+        let events = std::mem::take(&mut self.emitted_events);
+        let mut listeners = std::mem::take(&mut self.event_listeners);
+        for (event_id, payload) in events {
+            if let Some((_, listener)) = listeners.iter_mut().find(|(id, _)| *id == event_id) {
+                listener(payload, self);
+            }
+        }
+        if !self.emitted_events.is_empty() {
+            self.request_redraw();
+        }
+    }
+
+    /// makes sure another frame gets rendered right after the current one, e.g. while something is moving.
+    ///
+    /// Otherwise frames only get rendered after events or scheduled repaints.
+    pub fn request_redraw(&mut self) {
+        self.needs_redraw = true;
+    }
+
+    pub(crate) fn needs_redraw(&self) -> bool {
+        self.needs_redraw
+    }
+
+    /// how many frames have been rendered so far
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
     }
 
     pub(crate) fn next_frame(&mut self) {
+        // the styles read the theme of the renderer that is currently rendering
+        Theme::set_current(self.theme);
+        InheritedStyle::set_current(self.style_stack.top());
+        // the widgets request their redraws again while rendering if they still need them
+        self.needs_redraw = false;
+        self.scheduled_repaint = None;
         self.reset_cursor();
+        self.generated_id = GENERATED_ID_START;
+        self.scale_factor = self.backend.scale_factor();
         self.viewport = self.get_viewport();
-        self.frame = self.display.draw();
+        self.content_area = (0.0, 0.0, self.viewport.0, self.viewport.1);
+        self.frame = Some(self.backend.draw());
+        // the new frame has to be cleared before anything gets drawn, otherwise the first frame flashes
+        self.clear();
         self.frame_start = Instant::now();
     }
 
     pub(crate) fn done(&mut self) {
-        self.frame.set_finish().unwrap();
+        self.flush_draw_commands();
+        if self.cursor_icon != self.applied_cursor_icon {
+            let icon = self.cursor_icon;
+            self.backend.with_window(|window| window.set_cursor_icon(icon));
+            self.applied_cursor_icon = icon;
+        }
+        self.cursor_icon = CursorIcon::Default;
+        if let Some(frame) = self.frame.as_mut() {
+            frame.set_finish().unwrap();
+        }
         self.frame_time = self.frame_start.elapsed().as_nanos() as u32;
+        self.frame_count += 1;
+        self.frame_time_history.push(self.frame_time);
+        if self.frame_time_history.len() > self.frame_history_size {
+            self.frame_time_history.remove(0);
+        }
+        self.mouse.lmouseclick = false;
+        self.mouse.rmouseclick = false;
+        self.mouse.mmouseclick = false;
+        self.mouse.scroll_delta = 0.0;
         self.input.clear();
         self.keys.clear();
+        // a staggered animation that wasn't rendered this frame starts over the next time it shows up
+        let touched = &self.stagger_touched;
+        self.stagger_start.retain(|base_id, _| touched.contains(base_id));
+        self.stagger_touched.clear();
     }
 }
 
 pub enum Layout {
     Row { height: f32, x: f32, y: f32 },
     Col { width: f32, x: f32, y: f32 },
+    /// `x` and `y` point to the start of the current row
+    Grid { widths: Vec<f32>, column: usize, row_height: f32, x: f32, y: f32 },
+    /// `positions` is empty during the measuring pass of a flex row
+    Flex { sizes: Vec<(f32, f32)>, positions: Vec<(f32, f32)>, index: usize },
+}
+
+impl Layout {
+    /// moves `cursor` past a new shape of the given size
+    fn advance(&mut self, cursor: &mut (f32, f32), shape_width: f32, shape_height: f32) {
+        match self {
+            Layout::Row { height, .. } => {
+                cursor.0 += shape_width;
+                if shape_height > *height {
+                    *height = shape_height;
+                }
+            }
+            Layout::Col { width, .. } => {
+                cursor.1 += shape_height;
+                if shape_width > *width {
+                    *width = shape_width;
+                }
+            }
+            // while measuring there are no positions yet, so the items are just recorded
+            Layout::Flex { sizes, positions, .. } if positions.is_empty() => {
+                sizes.push((shape_width, shape_height));
+                cursor.0 += shape_width;
+            }
+            Layout::Flex { positions, index, .. } => {
+                *index += 1;
+                if let Some(pos) = positions.get(*index) {
+                    *cursor = *pos;
+                }
+            }
+            Layout::Grid { widths, column, row_height, x, y } => {
+                if shape_height > *row_height {
+                    *row_height = shape_height;
+                }
+                *column += 1;
+                if *column == widths.len() {
+                    *column = 0;
+                    *y += *row_height;
+                    *row_height = 0.0;
+                }
+                cursor.0 = *x + widths[..*column].iter().sum::<f32>();
+                cursor.1 = *y;
+            }
+        };
+    }
+
+    /// the size of the finished layout, `start` is the cursor position before it was started
+    fn size(&self, start: (f32, f32), cursor: (f32, f32)) -> (f32, f32) {
+        match self {
+            Layout::Row { height, .. } => (cursor.0 - start.0, *height),
+            Layout::Col { width, .. } => (*width, cursor.1 - start.1),
+            // `y` already points to the start of the current row
+            Layout::Grid { widths, row_height, y, .. } => (widths.iter().sum(), y + row_height - start.1),
+            Layout::Flex { sizes, .. } => (
+                sizes.iter().map(|(w, _)| w).sum(),
+                sizes.iter().map(|(_, h)| *h).fold(0.0, f32::max),
+            ),
+        }
+    }
+}
+
+/// how the items of a flex row get distributed horizontally
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Justify {
+    Start,
+    End,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+}
+
+/// how the items of a flex row get aligned vertically
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+    /// labels and buttons grow to the height of the row, other widgets are placed like `Start`
+    Stretch,
+}
+
+/// how the vertices of a shape form triangles
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum Triangles {
+    /// every vertex forms a triangle with the two before it
+    Strip,
+    /// every vertex forms a triangle with the one before it and the first one
+    Fan,
+    /// every three vertices form a triangle on their own
+    List,
+}
+
+/// the indices that turn the given primitive into a list of triangles
+fn triangulate(primitive: Triangles, count: usize) -> Vec<u16> {
+    let count = count as u16;
+    match primitive {
+        Triangles::Strip => (2..count).flat_map(|i| vec![i - 2, i - 1, i]).collect(),
+        Triangles::Fan => (2..count).flat_map(|i| vec![0, i - 1, i]).collect(),
+        Triangles::List => (0..count).collect(),
+    }
+}
+
+/// sorts the draw calls by their layer and merges neighbouring ones that can be drawn together
+fn batch_draw_commands(mut commands: Vec<DrawCommand>) -> Vec<DrawCommand> {
+    // the sort is stable, so draw calls on the same layer keep their order
+    commands.sort_by_key(|command| command.z);
+
+    let mut batched: Vec<DrawCommand> = Vec::with_capacity(commands.len());
+    for command in commands {
+        if let Some(last) = batched.last_mut() {
+            let same_program = match (&last.program, &command.program) {
+                (Some(program), Some(next_program)) => Rc::ptr_eq(program, next_program),
+                (None, None) => true,
+                _ => false,
+            };
+            if last.z == command.z && last.scissor == command.scissor && last.transform == command.transform && same_program {
+                match (&mut last.kind, &command.kind) {
+                    (
+                        DrawKind::Glyph { vertices, texture },
+                        DrawKind::Glyph { vertices: next_vertices, texture: next_texture },
+                    ) if Rc::ptr_eq(texture, next_texture) && vertices.len() + next_vertices.len() <= u16::MAX as usize => {
+                        vertices.extend_from_slice(next_vertices);
+                        continue;
+                    }
+                    (
+                        DrawKind::Shape { vertices, indices },
+                        DrawKind::Shape { vertices: next_vertices, indices: next_indices },
+                    ) if vertices.len() + next_vertices.len() <= u16::MAX as usize => {
+                        let offset = vertices.len() as u16;
+                        vertices.extend_from_slice(next_vertices);
+                        indices.extend(next_indices.iter().map(|i| i + offset));
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        batched.push(command);
+    }
+    batched
+}
+
+/// a draw call that gets executed when the frame is done
+#[derive(Clone)]
+pub(crate) struct DrawCommand {
+    z: i32,
+    scissor: Option<Rect>,
+    /// multiplied into the projection, see `with_transform`
+    transform: [[f32; 4]; 4],
+    /// replaces the default program for shapes and textures, see `with_shader`
+    program: Option<Rc<Program>>,
+    kind: DrawKind,
+}
+
+#[derive(Clone)]
+pub(crate) enum DrawKind {
+    /// `indices` always describe a list of triangles
+    Shape { vertices: Vec<Vertex>, indices: Vec<u16> },
+    Texture { vertices: Vec<Vertex>, texture_id: u32 },
+    Glyph { vertices: Vec<FontVertex>, texture: Rc<Texture2d> },
 }
 
 pub enum Texture {
-    Image(CompressedSrgbTexture2d),
+    Image { texture: SrgbTexture2d, has_alpha: bool },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Layout;
+
+    #[test]
+    fn row_moves_right_and_keeps_the_tallest_height() {
+        let mut cursor = (10.0, 20.0);
+        let mut row = Layout::Row { height: 0.0, x: cursor.0, y: cursor.1 };
+        row.advance(&mut cursor, 30.0, 5.0);
+        row.advance(&mut cursor, 20.0, 8.0);
+        assert_eq!(cursor, (60.0, 20.0));
+        assert_eq!(row.size((10.0, 20.0), cursor), (50.0, 8.0));
+    }
+
+    #[test]
+    fn grid_wraps_after_the_last_column() {
+        let mut cursor = (0.0, 0.0);
+        let mut grid = Layout::Grid { widths: vec![10.0, 20.0], column: 0, row_height: 0.0, x: 0.0, y: 0.0 };
+        grid.advance(&mut cursor, 10.0, 5.0);
+        assert_eq!(cursor, (10.0, 0.0));
+        grid.advance(&mut cursor, 20.0, 7.0);
+        assert_eq!(cursor, (0.0, 7.0));
+        grid.advance(&mut cursor, 10.0, 3.0);
+        assert_eq!(grid.size((0.0, 0.0), cursor), (30.0, 10.0));
+    }
+
+    #[test]
+    fn nested_row_advances_the_outer_column_by_its_size() {
+        let start = (0.0, 0.0);
+        let mut cursor = start;
+        let mut col = Layout::Col { width: 0.0, x: 0.0, y: 0.0 };
+        let mut row = Layout::Row { height: 0.0, x: 0.0, y: 0.0 };
+        row.advance(&mut cursor, 30.0, 5.0);
+        row.advance(&mut cursor, 20.0, 8.0);
+        let (width, height) = row.size(start, cursor);
+        cursor = start;
+        col.advance(&mut cursor, width, height);
+        assert_eq!(cursor, (0.0, 8.0));
+        assert_eq!(col.size(start, cursor), (50.0, 8.0));
+    }
+
+    #[test]
+    fn nested_col_advances_the_outer_row_by_its_size() {
+        let start = (0.0, 0.0);
+        let mut cursor = start;
+        let mut row = Layout::Row { height: 0.0, x: 0.0, y: 0.0 };
+        let mut col = Layout::Col { width: 0.0, x: 0.0, y: 0.0 };
+        col.advance(&mut cursor, 30.0, 5.0);
+        col.advance(&mut cursor, 20.0, 8.0);
+        let (width, height) = col.size(start, cursor);
+        cursor = start;
+        row.advance(&mut cursor, width, height);
+        assert_eq!(cursor, (30.0, 0.0));
+        assert_eq!(row.size(start, cursor), (30.0, 13.0));
+    }
 }
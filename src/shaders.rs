@@ -1,7 +1,7 @@
 pub const VERTEX_SHADER: &'static str = r#"
 #version 330 core
 layout (location = 0) in vec2 position;
-layout (location = 1) in vec3 color;
+layout (location = 1) in vec4 color;
 layout (location = 2) in vec2 tex_pos;
 
 uniform mat4 projection;
@@ -12,7 +12,7 @@ out vec2 vertex_tex_pos;
 void main()
 {
     gl_Position = projection * vec4(position, 0.0, 1.0);
-    vertex_color = vec4(color, 1.0);
+    vertex_color = color;
     vertex_tex_pos = tex_pos;
 }
 "#;
@@ -31,6 +31,7 @@ void main()
 {
     if (use_texture) {
         FragColor = texture(tex, vertex_tex_pos);
+        FragColor.a *= vertex_color.a;
     } else {
         FragColor = vertex_color;
     }
@@ -40,7 +41,7 @@ void main()
 pub const FONT_VERTEX_SHADER: &'static str = r#"
 #version 330 core
 layout (location = 0) in vec2 position;
-layout (location = 1) in vec3 color;
+layout (location = 1) in vec4 color;
 layout (location = 2) in vec2 tex_pos;
 
 uniform mat4 projection;
@@ -51,7 +52,7 @@ out vec2 vertex_tex_pos;
 void main()
 {
     gl_Position = projection * vec4(position, 0.0, 1.0);
-    vertex_color = vec4(color, 1.0);
+    vertex_color = color;
     vertex_tex_pos = tex_pos;
 }
 "#;
@@ -0,0 +1,22 @@
+use crate::color::Color;
+
+#[derive(Debug, Copy, Clone)]
+pub struct Border {
+    pub width: f32,
+    pub color: Color
+}
+
+impl Border {
+    pub fn new(width: f32, color: Color) -> Self {
+        Self {
+            width,
+            color
+        }
+    }
+}
+
+impl Into<Border> for (f32, Color) {
+    fn into(self) -> Border {
+        Border::new(self.0, self.1)
+    }
+}
@@ -0,0 +1,30 @@
+use crate::color::Color;
+use crate::theme::Theme;
+
+/// the ring drawn around a widget while it is focused through keyboard navigation
+#[derive(Debug, Copy, Clone)]
+pub struct FocusStyle {
+    pub color: Color,
+    pub thickness: f32
+}
+
+impl FocusStyle {
+    pub fn new(color: Color, thickness: f32) -> Self {
+        Self {
+            color,
+            thickness
+        }
+    }
+}
+
+impl Default for FocusStyle {
+    fn default() -> Self {
+        Self::new(Theme::current().accent_color, 2.0)
+    }
+}
+
+impl Into<FocusStyle> for (Color, f32) {
+    fn into(self) -> FocusStyle {
+        FocusStyle::new(self.0, self.1)
+    }
+}
@@ -0,0 +1,39 @@
+#[derive(Debug, Copy, Clone)]
+pub struct Margin {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32
+}
+
+impl Margin {
+    /// the same margin on every side
+    pub fn uniform(f: f32) -> Self {
+        Self::sides(f, f, f, f)
+    }
+
+    /// `h` on the left and right, `v` on the top and bottom
+    pub fn symmetric(h: f32, v: f32) -> Self {
+        Self::sides(h, v, h, v)
+    }
+
+    pub fn sides(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        Self {
+            left,
+            right,
+            top,
+            bottom,
+        }
+    }
+}
+
+impl Into<Margin> for f32 {
+    fn into(self) -> Margin {
+        Margin {
+            left: self,
+            right: self,
+            bottom: self,
+            top: self,
+        }
+    }
+}
@@ -1,3 +1,11 @@
+pub mod border;
+pub mod focus;
+pub mod margin;
 pub mod padding;
+pub mod scope;
 
+pub use border::Border;
+pub use focus::FocusStyle;
+pub use margin::Margin;
 pub use padding::Padding;
+pub use scope::{InheritedStyle, StyleScope, StyleStack};
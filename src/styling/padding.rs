@@ -6,35 +6,34 @@ pub struct Padding {
     pub bottom: f32
 }
 
-impl Into<Padding> for f32 {
-    fn into(self) -> Padding {
-        Padding {
-            left: self,
-            right: self,
-            bottom: self,
-            top: self,
-        }
+impl Padding {
+    /// the same padding on every side
+    pub fn uniform(f: f32) -> Self {
+        Self::sides(f, f, f, f)
     }
-}
 
-impl Into<Padding> for (f32, f32) {
-    fn into(self) -> Padding {
-        Padding {
-            left: self.0,
-            right: self.0,
-            bottom: self.1,
-            top: self.1,
+    /// `h` on the left and right, `v` on the top and bottom
+    pub fn symmetric(h: f32, v: f32) -> Self {
+        Self::sides(h, v, h, v)
+    }
+
+    pub fn sides(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        Self {
+            left,
+            right,
+            top,
+            bottom,
         }
     }
 }
 
-impl Into<Padding> for (f32, f32, f32, f32) {
+impl Into<Padding> for f32 {
     fn into(self) -> Padding {
         Padding {
-            left: self.0,
-            top: self.1,
-            right: self.2,
-            bottom: self.3,
+            left: self,
+            right: self,
+            bottom: self,
+            top: self,
         }
     }
 }
@@ -0,0 +1,89 @@
+use crate::color::Color;
+
+use std::cell::Cell;
+
+thread_local! {
+    /// the top of the style stack of the renderer that is currently rendering, read by the `Default` impls of the styles
+    static CURRENT_STYLE: Cell<InheritedStyle> = Cell::new(InheritedStyle::default());
+}
+
+/// the properties widgets inherit from `Renderer::with_style`, `None` falls back to the theme
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct InheritedStyle {
+    pub foreground_color: Option<Color>,
+    pub background_color: Option<Color>,
+    pub font_size: Option<u32>,
+}
+
+impl InheritedStyle {
+    /// `other` wins for every property it specifies
+    pub fn merge(self, other: InheritedStyle) -> Self {
+        Self {
+            foreground_color: other.foreground_color.or(self.foreground_color),
+            background_color: other.background_color.or(self.background_color),
+            font_size: other.font_size.or(self.font_size),
+        }
+    }
+
+    /// the inherited properties of the widget that is currently being rendered
+    pub fn current() -> Self {
+        CURRENT_STYLE.with(|style| style.get())
+    }
+
+    pub(crate) fn set_current(style: InheritedStyle) {
+        CURRENT_STYLE.with(|current| current.set(style));
+    }
+}
+
+/// anything that can be passed to `Renderer::with_style`
+pub trait StyleScope {
+    fn inherited(self) -> InheritedStyle;
+}
+
+impl StyleScope for InheritedStyle {
+    fn inherited(self) -> InheritedStyle {
+        self
+    }
+}
+
+/// only changes the foreground color
+impl StyleScope for Color {
+    fn inherited(self) -> InheritedStyle {
+        InheritedStyle {
+            foreground_color: Some(self),
+            ..Default::default()
+        }
+    }
+}
+
+/// only changes the font size
+impl StyleScope for u32 {
+    fn inherited(self) -> InheritedStyle {
+        InheritedStyle {
+            font_size: Some(self),
+            ..Default::default()
+        }
+    }
+}
+
+/// the merged styles of the nested `with_style` calls
+#[derive(Debug, Default)]
+pub struct StyleStack {
+    stack: Vec<InheritedStyle>,
+}
+
+impl StyleStack {
+    pub fn push<S: StyleScope>(&mut self, style: S) {
+        let merged = self.top().merge(style.inherited());
+        self.stack.push(merged);
+    }
+
+    pub fn pop(&mut self) -> Option<InheritedStyle> {
+        self.stack.pop()
+    }
+
+    /// the merged style, empty outside of `with_style`
+    pub fn top(&self) -> InheritedStyle {
+        self.stack.last().copied().unwrap_or_default()
+    }
+}
@@ -0,0 +1,78 @@
+use crate::color::Color;
+
+use std::cell::Cell;
+
+thread_local! {
+    /// the theme the `Default` impls of the styles read from, see `Renderer::set_theme`
+    static CURRENT_THEME: Cell<Theme> = Cell::new(Theme::light());
+}
+
+/// the default colors of all the built-in widgets
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Theme {
+    /// the background of the window
+    pub background_color: Color,
+    /// text and icons
+    pub foreground_color: Color,
+    /// widgets that show content, e.g. inputs, table rows and modals
+    pub surface_color: Color,
+    /// every second row of a table
+    pub alternate_surface_color: Color,
+    /// widgets that can be clicked, e.g. buttons and accordion headers
+    pub control_color: Color,
+    /// controls while hovered, also used for tracks and headers
+    pub hover_color: Color,
+    /// highlights, e.g. the focus ring, selections and the filled part of a slider
+    pub accent_color: Color,
+    /// the thumb of a slider
+    pub thumb_color: Color,
+    pub tooltip_background_color: Color,
+    pub tooltip_foreground_color: Color,
+}
+
+impl Theme {
+    pub fn light() -> Self {
+        Self {
+            background_color: Color::new(230, 230, 230),
+            foreground_color: Color::BLACK,
+            surface_color: Color::WHITE,
+            alternate_surface_color: Color::new(240, 240, 240),
+            control_color: Color::new(220, 220, 220),
+            hover_color: Color::new(200, 200, 200),
+            accent_color: Color::new(0, 120, 215),
+            thumb_color: Color::new(80, 80, 80),
+            tooltip_background_color: Color::new(50, 50, 50),
+            tooltip_foreground_color: Color::WHITE,
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            background_color: Color::new(30, 30, 30),
+            foreground_color: Color::new(230, 230, 230),
+            surface_color: Color::new(45, 45, 45),
+            alternate_surface_color: Color::new(55, 55, 55),
+            control_color: Color::new(65, 65, 65),
+            hover_color: Color::new(85, 85, 85),
+            accent_color: Color::new(50, 150, 240),
+            thumb_color: Color::new(200, 200, 200),
+            tooltip_background_color: Color::new(220, 220, 220),
+            tooltip_foreground_color: Color::BLACK,
+        }
+    }
+
+    /// the theme of the renderer that is currently rendering on this thread
+    pub fn current() -> Self {
+        CURRENT_THEME.with(|theme| theme.get())
+    }
+
+    pub(crate) fn set_current(theme: Theme) {
+        CURRENT_THEME.with(|current| current.set(theme));
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
@@ -10,7 +10,7 @@ use crate::color::Color;
 #[derive(Copy, Clone, Debug)]
 pub struct Vertex {
     position: [f32; 2],
-    color: [f32; 3],
+    color: [f32; 4],
     tex_pos: [f32; 2],
 }
 
@@ -27,16 +27,25 @@ impl Vertex {
     pub fn textured(tex_pos: (f32, f32), x: f32, y: f32) -> Self {
         Self {
             position: [x, y],
-            color: [0.0, 0.0, 0.0],
+            // only the alpha is used for textures, see `FRAGMENT_SHADER`
+            color: [1.0, 1.0, 1.0, 1.0],
             tex_pos: [tex_pos.0, tex_pos.1],
         }
     }
+
+    pub(crate) fn multiply_alpha(&mut self, alpha: f32) {
+        self.color[3] *= alpha;
+    }
+
+    pub(crate) fn is_translucent(&self) -> bool {
+        self.color[3] < 1.0
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
 pub struct FontVertex {
     pub position: [f32; 2],
-    pub color: [f32; 3],
+    pub color: [f32; 4],
     pub tex_pos: [f32; 2],
 }
 
@@ -0,0 +1,87 @@
+use winit::{
+    dpi::LogicalSize,
+    window::{Icon, WindowBuilder},
+};
+
+use crate::error::IconLoadError;
+
+/// decodes a png/ico/... buffer into a window icon
+pub fn load_icon(data: &[u8]) -> Result<Icon, IconLoadError> {
+    let image = image::load_from_memory(data)?.to_rgba8();
+    let (width, height) = image.dimensions();
+    Ok(Icon::from_rgba(image.into_raw(), width, height)?)
+}
+
+/// the most common window settings in one place, meant to be applied in `Application::window`
+#[derive(Debug, Default)]
+pub struct WindowConfig {
+    pub title: Option<String>,
+    /// in logical pixels
+    pub size: Option<(f32, f32)>,
+    pub icon: Option<Icon>,
+    /// makes the parts of the window that are drawn with a translucent background color see-through
+    pub transparent: bool,
+    /// whether the window has a title bar and borders, the platform default if `None`
+    pub decorations: Option<bool>,
+}
+
+impl WindowConfig {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    pub fn icon(mut self, icon: Icon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
+    }
+
+    pub fn decorations(mut self, decorations: bool) -> Self {
+        self.decorations = Some(decorations);
+        self
+    }
+
+    /// like `icon`, but decodes the icon from a png/ico/... buffer first
+    pub fn icon_from_bytes(self, data: &[u8]) -> Result<Self, IconLoadError> {
+        Ok(self.icon(load_icon(data)?))
+    }
+
+    pub fn apply(self, mut w: WindowBuilder) -> WindowBuilder {
+        if let Some(title) = self.title {
+            w = w.with_title(title);
+        }
+        if let Some((width, height)) = self.size {
+            w = w.with_inner_size(LogicalSize::new(width, height));
+        }
+        if self.icon.is_some() {
+            w = w.with_window_icon(self.icon);
+        }
+        if self.transparent {
+            w = w.with_transparent(true);
+        }
+        if let Some(decorations) = self.decorations {
+            w = w.with_decorations(decorations);
+        }
+        w
+    }
+}
+
+impl Into<WindowBuilder> for WindowConfig {
+    fn into(self) -> WindowBuilder {
+        self.apply(WindowBuilder::new())
+    }
+}